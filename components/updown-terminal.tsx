@@ -19,6 +19,8 @@ import {
   derivePositionPda,
   deriveRoundPda,
   estimatePositionPayoutLamports,
+  fetchActiveSeason,
+  fetchReferrer,
   marketKeyToCode,
   roundStartMsFromRoundId
 } from "@/lib/onchain-pvp";
@@ -623,7 +625,7 @@ export default function UpDownTerminal() {
           continue;
         }
 
-        const payout = round.status === ROUND_STATUS_SETTLED ? estimatePositionPayoutLamports(round, position) : BigInt(0);
+        const payout = round.status === ROUND_STATUS_SETTLED ? estimatePositionPayoutLamports(round, position, PLATFORM_FEE_BPS) : BigInt(0);
         next[receipt.signature] = {
           roundStatus: toRoundStatusLabel(round.status),
           claimableLamports: Number(payout),
@@ -677,7 +679,9 @@ export default function UpDownTerminal() {
         user,
         marketKey: receipt.market,
         roundStartMs,
-        direction: receipt.direction
+        direction: receipt.direction,
+        referrer: await fetchReferrer(connection, user),
+        season: await fetchActiveSeason(connection)
       });
 
       const latest = await connection.getLatestBlockhash("confirmed");
@@ -912,7 +916,8 @@ export default function UpDownTerminal() {
         marketKey: selectedMarket.key,
         roundStartMs: currentRound.startMs,
         direction,
-        lamports
+        lamports,
+        season: USE_ONCHAIN_PROGRAM ? await fetchActiveSeason(connection) : undefined
       });
       const to = new PublicKey(ESCROW_WALLET);
       const memo = `PANCHO|${selectedMarket.key}|${currentRound.roundId}|${direction}|${stake}`;
@@ -1,4 +1,4 @@
-import { PublicKey, SystemProgram, TransactionInstruction } from "@solana/web3.js";
+import { Connection, PublicKey, SystemProgram, TransactionInstruction } from "@solana/web3.js";
 import { Buffer } from "buffer";
 
 export const PANCHO_PROGRAM_ID = new PublicKey(
@@ -10,23 +10,43 @@ export const CLAIM_DISCRIMINATOR = Buffer.from([62, 198, 214, 193, 213, 159, 108
 // Must match current Anchor discriminator for account:Round.
 const ROUND_ACCOUNT_DISCRIMINATOR = Buffer.from([87, 127, 165, 51, 73, 78, 116, 174]);
 const POSITION_ACCOUNT_DISCRIMINATOR = Buffer.from([170, 188, 143, 228, 122, 64, 247, 208]);
+const USER_STATS_ACCOUNT_DISCRIMINATOR = Buffer.from([176, 223, 136, 27, 122, 79, 32, 227]);
+const GLOBAL_CONFIG_ACCOUNT_DISCRIMINATOR = Buffer.from([149, 8, 156, 202, 160, 252, 176, 217]);
+// onchain/tests/pancho_pvp.ts checks this against the GlobalConfig layout in the IDL.
+const GLOBAL_CONFIG_ACTIVE_SEASON_OFFSET = 500;
 
 export type OnchainRoundState = {
   status: number;
   winnerSide: number;
   lockTs: number;
   endTs: number;
+  entryCutoffTs: number;
   distributableLamports: bigint;
   upTotal: bigint;
   downTotal: bigint;
+  upWeight: bigint;
+  downWeight: bigint;
 };
 
 export type OnchainPositionState = {
   side: number;
   amountLamports: bigint;
+  weight: bigint;
   claimed: boolean;
 };
 
+export type OnchainUserStats = {
+  user: PublicKey;
+  totalStakedLamports: bigint;
+  totalPayoutLamports: bigint;
+  roundsPlayed: number;
+  wins: number;
+  losses: number;
+  voids: number;
+  currentStreak: number;
+  bestStreak: number;
+};
+
 export function marketKeyToCode(market: string): number {
   const key = market.toUpperCase();
   if (key === "SOL") return 0;
@@ -82,12 +102,70 @@ export function derivePositionPda(round: PublicKey, user: PublicKey, side: numbe
   )[0];
 }
 
+export function deriveReferralPda(user: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync([Buffer.from("referral"), user.toBuffer()], PANCHO_PROGRAM_ID)[0];
+}
+
+export function deriveReferrerRewardsPda(referrer: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync([Buffer.from("referrer_rewards"), referrer.toBuffer()], PANCHO_PROGRAM_ID)[0];
+}
+
+export function deriveUserStatsPda(user: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync([Buffer.from("user_stats"), user.toBuffer()], PANCHO_PROGRAM_ID)[0];
+}
+
+export function deriveSeasonStatsPda(season: PublicKey, user: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("season_stats"), season.toBuffer(), user.toBuffer()],
+    PANCHO_PROGRAM_ID
+  )[0];
+}
+
+// The default key in GlobalConfig.active_season means no season is running.
+export async function fetchActiveSeason(connection: Connection): Promise<PublicKey | undefined> {
+  const info = await connection.getAccountInfo(deriveConfigPda(), "confirmed");
+  const end = GLOBAL_CONFIG_ACTIVE_SEASON_OFFSET + 32;
+  if (
+    !info ||
+    !info.owner.equals(PANCHO_PROGRAM_ID) ||
+    info.data.length < end ||
+    !info.data.subarray(0, 8).equals(GLOBAL_CONFIG_ACCOUNT_DISCRIMINATOR)
+  ) {
+    return undefined;
+  }
+  const season = new PublicKey(info.data.subarray(GLOBAL_CONFIG_ACTIVE_SEASON_OFFSET, end));
+  return season.equals(PublicKey.default) ? undefined : season;
+}
+
+// Referral.referrer sits at byte offset 40; claims need the referrer's rewards account when it is set.
+export async function fetchReferrer(connection: Connection, user: PublicKey): Promise<PublicKey | undefined> {
+  const info = await connection.getAccountInfo(deriveReferralPda(user), "confirmed");
+  if (!info || !info.owner.equals(PANCHO_PROGRAM_ID) || info.data.length < 72) {
+    return undefined;
+  }
+  return new PublicKey(info.data.subarray(40, 72));
+}
+
+// Season accounts are mandatory while a season is active; otherwise the program id stands in.
+function seasonAccountMetas(user: PublicKey, season?: PublicKey) {
+  return [
+    { pubkey: season ?? PANCHO_PROGRAM_ID, isSigner: false, isWritable: Boolean(season) },
+    {
+      pubkey: season ? deriveSeasonStatsPda(season, user) : PANCHO_PROGRAM_ID,
+      isSigner: false,
+      isWritable: Boolean(season)
+    }
+  ];
+}
+
 export function buildJoinRoundInstruction(params: {
   user: PublicKey;
   marketKey: string;
   roundStartMs: number;
   direction: "UP" | "DOWN";
   lamports: number;
+  minMultiplierBps?: number;
+  season?: PublicKey;
 }): TransactionInstruction {
   const marketCode = marketKeyToCode(params.marketKey);
   const roundId = roundIdFromStartMs(params.roundStartMs);
@@ -98,10 +176,16 @@ export function buildJoinRoundInstruction(params: {
   const position = derivePositionPda(round, params.user, side);
   const sideVault = deriveVaultPda(round, side);
 
-  const data = Buffer.alloc(8 + 1 + 8);
+  const hasMinMultiplier = params.minMultiplierBps !== undefined;
+  // Trailing u32 is the length of the allowlist proof, which public rounds leave empty.
+  const data = Buffer.alloc(8 + 1 + 8 + 1 + (hasMinMultiplier ? 8 : 0) + 4);
   JOIN_ROUND_DISCRIMINATOR.copy(data, 0);
   data.writeUInt8(side, 8);
   data.writeBigUInt64LE(BigInt(Math.floor(params.lamports)), 9);
+  data.writeUInt8(hasMinMultiplier ? 1 : 0, 17);
+  if (hasMinMultiplier) {
+    data.writeBigUInt64LE(BigInt(Math.floor(params.minMultiplierBps ?? 0)), 18);
+  }
 
   return new TransactionInstruction({
     programId: PANCHO_PROGRAM_ID,
@@ -111,7 +195,10 @@ export function buildJoinRoundInstruction(params: {
       { pubkey: round, isSigner: false, isWritable: true },
       { pubkey: position, isSigner: false, isWritable: true },
       { pubkey: sideVault, isSigner: false, isWritable: true },
-      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: deriveUserStatsPda(params.user), isSigner: false, isWritable: true },
+      ...seasonAccountMetas(params.user, params.season),
+      { pubkey: PANCHO_PROGRAM_ID, isSigner: false, isWritable: false }
     ],
     data
   });
@@ -122,6 +209,8 @@ export function buildClaimInstruction(params: {
   marketKey: string;
   roundStartMs: number;
   direction: "UP" | "DOWN";
+  referrer?: PublicKey;
+  season?: PublicKey;
 }): TransactionInstruction {
   const marketCode = marketKeyToCode(params.marketKey);
   const roundId = roundIdFromStartMs(params.roundStartMs);
@@ -131,15 +220,26 @@ export function buildClaimInstruction(params: {
   const position = derivePositionPda(round, params.user, side);
   const upVault = deriveVaultPda(round, 0);
   const downVault = deriveVaultPda(round, 1);
+  // The referral PDA is always required; the program reads it when it exists.
+  const referral = deriveReferralPda(params.user);
+  // Optional accounts: passing the program id tells Anchor the account is absent.
+  const referrerRewards = params.referrer ? deriveReferrerRewardsPda(params.referrer) : PANCHO_PROGRAM_ID;
+  // Every join creates the user's UserStats PDA, and claims read its fee tier from it.
+  const userStats = deriveUserStatsPda(params.user);
 
   return new TransactionInstruction({
     programId: PANCHO_PROGRAM_ID,
     keys: [
       { pubkey: params.user, isSigner: true, isWritable: true },
+      { pubkey: deriveConfigPda(), isSigner: false, isWritable: false },
       { pubkey: round, isSigner: false, isWritable: true },
       { pubkey: position, isSigner: false, isWritable: true },
       { pubkey: upVault, isSigner: false, isWritable: true },
-      { pubkey: downVault, isSigner: false, isWritable: true }
+      { pubkey: downVault, isSigner: false, isWritable: true },
+      { pubkey: referral, isSigner: false, isWritable: false },
+      { pubkey: referrerRewards, isSigner: false, isWritable: Boolean(params.referrer) },
+      { pubkey: userStats, isSigner: false, isWritable: true },
+      ...seasonAccountMetas(params.user, params.season)
     ],
     data: CLAIM_DISCRIMINATOR
   });
@@ -149,14 +249,19 @@ export function decodeRoundAccount(data: Buffer): OnchainRoundState | null {
   if (data.length < 152 || !data.subarray(0, 8).equals(ROUND_ACCOUNT_DISCRIMINATOR)) {
     return null;
   }
+  const lockTs = Number(data.readBigInt64LE(81));
   return {
-    lockTs: Number(data.readBigInt64LE(81)),
+    lockTs,
     endTs: Number(data.readBigInt64LE(89)),
+    entryCutoffTs: data.length >= 216 ? Number(data.readBigInt64LE(208)) : lockTs,
     status: data.readUInt8(117),
     winnerSide: data.readUInt8(118),
     upTotal: data.readBigUInt64LE(119),
     downTotal: data.readBigUInt64LE(127),
-    distributableLamports: data.readBigUInt64LE(143)
+    distributableLamports: data.readBigUInt64LE(143),
+    // Time-weighted rounds pay winners by weight; plain rounds keep weight == stake.
+    upWeight: data.length >= 243 ? data.readBigUInt64LE(227) : data.readBigUInt64LE(119),
+    downWeight: data.length >= 243 ? data.readBigUInt64LE(235) : data.readBigUInt64LE(127)
   };
 }
 
@@ -164,14 +269,38 @@ export function decodePositionAccount(data: Buffer): OnchainPositionState | null
   if (data.length < 83 || !data.subarray(0, 8).equals(POSITION_ACCOUNT_DISCRIMINATOR)) {
     return null;
   }
+  const amountLamports = data.readBigUInt64LE(73);
   return {
     side: data.readUInt8(72),
-    amountLamports: data.readBigUInt64LE(73),
+    amountLamports,
+    weight: data.length >= 91 ? data.readBigUInt64LE(83) : amountLamports,
     claimed: data.readUInt8(81) === 1
   };
 }
 
-export function estimatePositionPayoutLamports(round: OnchainRoundState, position: OnchainPositionState): bigint {
+export function decodeUserStatsAccount(data: Buffer): OnchainUserStats | null {
+  if (data.length < 329 || !data.subarray(0, 8).equals(USER_STATS_ACCOUNT_DISCRIMINATOR)) {
+    return null;
+  }
+  return {
+    user: new PublicKey(data.subarray(8, 40)),
+    totalStakedLamports: data.readBigUInt64LE(289),
+    totalPayoutLamports: data.readBigUInt64LE(297),
+    roundsPlayed: data.readUInt32LE(305),
+    wins: data.readUInt32LE(309),
+    losses: data.readUInt32LE(313),
+    voids: data.readUInt32LE(317),
+    currentStreak: data.readUInt32LE(321),
+    bestStreak: data.readUInt32LE(325)
+  };
+}
+
+// Winners pay `feeBps` of their gross payout at claim; pass the base rate for a conservative estimate.
+export function estimatePositionPayoutLamports(
+  round: OnchainRoundState,
+  position: OnchainPositionState,
+  feeBps = 0
+): bigint {
   const zero = BigInt(0);
   if (position.amountLamports === zero) {
     return zero;
@@ -186,9 +315,10 @@ export function estimatePositionPayoutLamports(round: OnchainRoundState, positio
   if (position.side !== round.winnerSide) {
     return zero;
   }
-  const winnerTotal = round.winnerSide === 0 ? round.upTotal : round.downTotal;
-  if (winnerTotal <= zero) {
+  const winnerWeight = round.winnerSide === 0 ? round.upWeight : round.downWeight;
+  if (winnerWeight <= zero) {
     return zero;
   }
-  return (position.amountLamports * round.distributableLamports) / winnerTotal;
+  const gross = (position.weight * round.distributableLamports) / winnerWeight;
+  return gross - (gross * BigInt(feeBps)) / BigInt(10_000);
 }
@@ -14,62 +14,26 @@ export type PanchoPvp = {
   },
   "instructions": [
     {
-      "name": "claim",
-      "discriminator": [
-        62,
-        198,
-        214,
-        193,
-        213,
-        159,
-        108,
-        210
-      ],
-      "accounts": [
-        {
-          "name": "user",
-          "writable": true,
-          "signer": true
-        },
-        {
-          "name": "round",
-          "writable": true
-        },
-        {
-          "name": "position",
-          "writable": true
-        },
-        {
-          "name": "upVault",
-          "writable": true
-        },
-        {
-          "name": "downVault",
-          "writable": true
-        }
+      "name": "acceptDuel",
+      "docs": [
+        "Takes the opposite side with the same stake. The start price is read afterwards by",
+        "`lock_duel`, so it cannot predate the acceptance."
       ],
-      "args": []
-    },
-    {
-      "name": "createRound",
       "discriminator": [
-        229,
-        218,
-        236,
-        169,
-        231,
         80,
-        134,
-        112
+        52,
+        90,
+        135,
+        172,
+        221,
+        175,
+        102
       ],
       "accounts": [
         {
-          "name": "admin",
+          "name": "counterparty",
           "writable": true,
-          "signer": true,
-          "relations": [
-            "config"
-          ]
+          "signer": true
         },
         {
           "name": "config",
@@ -90,16 +54,31 @@ export type PanchoPvp = {
           }
         },
         {
-          "name": "round",
-          "writable": true
-        },
-        {
-          "name": "upVault",
-          "writable": true
-        },
-        {
-          "name": "downVault",
-          "writable": true
+          "name": "duel",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  117,
+                  101,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "duel.challenger",
+                "account": "duel"
+              },
+              {
+                "kind": "account",
+                "path": "duel.duel_id",
+                "account": "duel"
+              }
+            ]
+          }
         },
         {
           "name": "systemProgram",
@@ -108,120 +87,75 @@ export type PanchoPvp = {
       ],
       "args": [
         {
-          "name": "market",
-          "type": "u8"
-        },
-        {
-          "name": "roundId",
-          "type": "i64"
-        },
-        {
-          "name": "lockTs",
-          "type": "i64"
-        },
-        {
-          "name": "endTs",
-          "type": "i64"
-        },
-        {
-          "name": "feedId",
-          "type": {
-            "array": [
-              "u8",
-              32
-            ]
-          }
-        },
-        {
-          "name": "oraclePriceAccount",
-          "type": "pubkey"
+          "name": "stakeLamports",
+          "type": "u64"
         }
       ]
     },
     {
-      "name": "initializeConfig",
+      "name": "cancelDuel",
+      "docs": [
+        "Refunds an unaccepted duel after its expiry and closes it to the challenger."
+      ],
       "discriminator": [
-        208,
-        127,
-        21,
-        1,
-        194,
-        190,
-        196,
-        70
+        83,
+        124,
+        224,
+        237,
+        235,
+        44,
+        38,
+        57
       ],
       "accounts": [
         {
-          "name": "admin",
-          "writable": true,
-          "signer": true,
-          "address": "Dkm5UeGTaeXDkauBMtNwbHGw7q2aXbrqb9HBQVN5GFx8"
-        },
-        {
-          "name": "treasury"
-        },
-        {
-          "name": "config",
+          "name": "duel",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  99,
-                  111,
-                  110,
-                  102,
-                  105,
-                  103
+                  100,
+                  117,
+                  101,
+                  108
                 ]
+              },
+              {
+                "kind": "account",
+                "path": "duel.challenger",
+                "account": "duel"
+              },
+              {
+                "kind": "account",
+                "path": "duel.duel_id",
+                "account": "duel"
               }
             ]
           }
         },
         {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
+          "name": "challenger",
+          "writable": true,
+          "relations": [
+            "duel"
+          ]
         }
       ],
-      "args": [
-        {
-          "name": "feeBps",
-          "type": "u16"
-        },
-        {
-          "name": "oracleMaxAgeSec",
-          "type": "u32"
-        },
-        {
-          "name": "oracleProgram",
-          "type": "pubkey"
-        },
-        {
-          "name": "oracleAccountSol",
-          "type": "pubkey"
-        },
-        {
-          "name": "oracleAccountBtc",
-          "type": "pubkey"
-        },
-        {
-          "name": "oracleAccountEth",
-          "type": "pubkey"
-        }
-      ]
+      "args": []
     },
     {
-      "name": "joinRound",
+      "name": "claim",
       "discriminator": [
-        191,
-        222,
-        86,
-        25,
-        234,
-        174,
-        157,
-        249
+        62,
+        198,
+        214,
+        193,
+        213,
+        159,
+        108,
+        210
       ],
       "accounts": [
         {
@@ -256,146 +190,229 @@ export type PanchoPvp = {
           "writable": true
         },
         {
-          "name": "sideVault",
+          "name": "upVault",
           "writable": true
         },
         {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
-        }
-      ],
-      "args": [
-        {
-          "name": "side",
-          "type": "u8"
+          "name": "downVault",
+          "writable": true
         },
         {
-          "name": "lamports",
-          "type": "u64"
-        }
-      ]
-    },
-    {
-      "name": "lockRound",
-      "discriminator": [
-        68,
-        124,
-        43,
-        230,
-        30,
-        44,
-        248,
-        227
-      ],
-      "accounts": [
+          "name": "referral",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  102,
+                  101,
+                  114,
+                  114,
+                  97,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
         {
-          "name": "config",
+          "name": "referrerRewards",
+          "writable": true,
+          "optional": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  99,
-                  111,
-                  110,
+                  114,
+                  101,
                   102,
-                  105,
-                  103
+                  101,
+                  114,
+                  114,
+                  101,
+                  114,
+                  95,
+                  114,
+                  101,
+                  119,
+                  97,
+                  114,
+                  100,
+                  115
                 ]
+              },
+              {
+                "kind": "account",
+                "path": "referrer_rewards.referrer",
+                "account": "referrerRewards"
               }
             ]
           }
         },
         {
-          "name": "round",
-          "writable": true
+          "name": "userStats",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
         },
         {
-          "name": "oraclePrice"
+          "name": "season",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "seasonStats",
+          "writable": true,
+          "optional": true
         }
       ],
       "args": []
     },
     {
-      "name": "setConfig",
+      "name": "claimChips",
+      "docs": [
+        "Permissionless, so a settled round's chip positions can always be cleared before",
+        "`close_round`. Chips credited after the table freezes no longer move the ranking."
+      ],
       "discriminator": [
-        108,
-        158,
+        145,
+        205,
         154,
-        175,
-        212,
-        98,
-        52,
-        66
+        242,
+        241,
+        150,
+        215,
+        26
       ],
       "accounts": [
         {
-          "name": "admin",
-          "writable": true,
-          "signer": true,
-          "relations": [
-            "config"
-          ]
+          "name": "claimer",
+          "signer": true
         },
         {
-          "name": "config",
+          "name": "user",
+          "writable": true
+        },
+        {
+          "name": "tournament",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  99,
+                  116,
                   111,
+                  117,
+                  114,
                   110,
-                  102,
-                  105,
-                  103
+                  97,
+                  109,
+                  101,
+                  110,
+                  116
                 ]
+              },
+              {
+                "kind": "account",
+                "path": "tournament.tournament_id",
+                "account": "tournament"
               }
             ]
           }
-        }
-      ],
-      "args": [
+        },
         {
-          "name": "feeBps",
-          "type": "u16"
+          "name": "entrant",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  110,
+                  116,
+                  114,
+                  97,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "tournament"
+              },
+              {
+                "kind": "account",
+                "path": "entrant.user",
+                "account": "entrant"
+              }
+            ]
+          }
         },
         {
-          "name": "oracleMaxAgeSec",
-          "type": "u32"
+          "name": "round",
+          "writable": true
         },
         {
-          "name": "paused",
-          "type": "bool"
+          "name": "chipPosition",
+          "writable": true
         }
-      ]
+      ],
+      "args": []
     },
     {
-      "name": "setOracleAccounts",
+      "name": "claimFor",
+      "docs": [
+        "Permissionless claim: the payout always goes to `position.user`, and the caller",
+        "earns a tip out of the crank share of the position's fee."
+      ],
       "discriminator": [
-        174,
-        106,
-        146,
-        87,
-        199,
-        228,
-        200,
-        208
+        245,
+        67,
+        97,
+        44,
+        59,
+        223,
+        144,
+        1
       ],
       "accounts": [
         {
-          "name": "admin",
+          "name": "cranker",
           "writable": true,
-          "signer": true,
-          "relations": [
-            "config"
-          ]
+          "signer": true
         },
         {
           "name": "config",
-          "writable": true,
           "pda": {
             "seeds": [
               {
@@ -411,82 +428,200 @@ export type PanchoPvp = {
               }
             ]
           }
-        }
-      ],
-      "args": [
+        },
         {
-          "name": "oracleAccountSol",
-          "type": "pubkey"
+          "name": "round",
+          "writable": true
         },
         {
-          "name": "oracleAccountBtc",
-          "type": "pubkey"
+          "name": "position",
+          "writable": true
         },
         {
-          "name": "oracleAccountEth",
-          "type": "pubkey"
+          "name": "user",
+          "writable": true
+        },
+        {
+          "name": "upVault",
+          "writable": true
+        },
+        {
+          "name": "downVault",
+          "writable": true
+        },
+        {
+          "name": "referral",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  102,
+                  101,
+                  114,
+                  114,
+                  97,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "position.user",
+                "account": "position"
+              }
+            ]
+          }
+        },
+        {
+          "name": "referrerRewards",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  102,
+                  101,
+                  114,
+                  114,
+                  101,
+                  114,
+                  95,
+                  114,
+                  101,
+                  119,
+                  97,
+                  114,
+                  100,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "referrer_rewards.referrer",
+                "account": "referrerRewards"
+              }
+            ]
+          }
+        },
+        {
+          "name": "userStats",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "position.user",
+                "account": "position"
+              }
+            ]
+          }
+        },
+        {
+          "name": "season",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "seasonStats",
+          "writable": true,
+          "optional": true
         }
-      ]
+      ],
+      "args": []
     },
     {
-      "name": "setTreasury",
+      "name": "claimHouse",
       "discriminator": [
-        57,
-        97,
-        196,
-        95,
-        195,
-        206,
-        106,
-        136
+        223,
+        134,
+        212,
+        224,
+        218,
+        174,
+        228,
+        130
       ],
       "accounts": [
         {
-          "name": "admin",
-          "writable": true,
-          "signer": true,
-          "relations": [
-            "config"
-          ]
+          "name": "round",
+          "writable": true
         },
         {
-          "name": "config",
+          "name": "houseVault",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  99,
+                  104,
                   111,
-                  110,
-                  102,
-                  105,
-                  103
+                  117,
+                  115,
+                  101
                 ]
               }
             ]
           }
         },
         {
-          "name": "newTreasury"
+          "name": "position",
+          "writable": true
+        },
+        {
+          "name": "upVault",
+          "writable": true
+        },
+        {
+          "name": "downVault",
+          "writable": true
         }
       ],
       "args": []
     },
     {
-      "name": "settleRound",
+      "name": "claimMany",
+      "docs": [
+        "Claims several positions owned by `user` in one instruction. `remaining_accounts`",
+        "holds one `(round, position, up_vault, down_vault)` tuple per position."
+      ],
       "discriminator": [
-        40,
-        101,
-        18,
-        1,
-        31,
-        129,
-        52,
-        77
+        239,
+        76,
+        176,
+        190,
+        112,
+        53,
+        176,
+        100
       ],
       "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
         {
           "name": "config",
           "pda": {
@@ -506,294 +641,7922 @@ export type PanchoPvp = {
           }
         },
         {
-          "name": "round",
-          "writable": true
+          "name": "referral",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  102,
+                  101,
+                  114,
+                  114,
+                  97,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
         },
         {
-          "name": "upVault",
-          "writable": true
+          "name": "referrerRewards",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  102,
+                  101,
+                  114,
+                  114,
+                  101,
+                  114,
+                  95,
+                  114,
+                  101,
+                  119,
+                  97,
+                  114,
+                  100,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "referrer_rewards.referrer",
+                "account": "referrerRewards"
+              }
+            ]
+          }
         },
         {
-          "name": "downVault",
-          "writable": true
+          "name": "userStats",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
         },
         {
-          "name": "oraclePrice"
+          "name": "season",
+          "writable": true,
+          "optional": true
         },
         {
-          "name": "treasury",
-          "writable": true
+          "name": "seasonStats",
+          "writable": true,
+          "optional": true
         }
       ],
       "args": []
-    }
-  ],
-  "accounts": [
+    },
     {
-      "name": "globalConfig",
+      "name": "claimReferralRewards",
       "discriminator": [
-        149,
-        8,
-        156,
-        202,
-        160,
-        252,
-        176,
-        217
-      ]
+        23,
+        112,
+        76,
+        162,
+        157,
+        106,
+        203,
+        246
+      ],
+      "accounts": [
+        {
+          "name": "referrer",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "referrerRewards"
+          ]
+        },
+        {
+          "name": "referrerRewards",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  102,
+                  101,
+                  114,
+                  114,
+                  101,
+                  114,
+                  95,
+                  114,
+                  101,
+                  119,
+                  97,
+                  114,
+                  100,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "referrer"
+              }
+            ]
+          }
+        }
+      ],
+      "args": []
     },
     {
-      "name": "position",
+      "name": "claimSeasonPrize",
       "discriminator": [
-        170,
-        188,
-        143,
-        228,
-        122,
-        64,
-        247,
-        208
-      ]
+        151,
+        171,
+        59,
+        18,
+        116,
+        101,
+        22,
+        202
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "season",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  97,
+                  115,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "season.season_id",
+                "account": "season"
+              }
+            ]
+          }
+        }
+      ],
+      "args": []
     },
     {
-      "name": "round",
+      "name": "claimTournamentPrize",
       "discriminator": [
-        87,
-        127,
-        165,
-        51,
-        73,
+        219,
+        207,
+        183,
+        94,
+        201,
+        32,
         78,
-        116,
-        174
-      ]
+        193
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tournament",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  116,
+                  111,
+                  117,
+                  114,
+                  110,
+                  97,
+                  109,
+                  101,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "tournament.tournament_id",
+                "account": "tournament"
+              }
+            ]
+          }
+        }
+      ],
+      "args": []
     },
     {
-      "name": "vault",
-      "discriminator": [
-        211,
-        8,
-        232,
-        43,
-        2,
-        152,
-        117,
-        119
-      ]
-    }
-  ],
-  "events": [
-    {
-      "name": "claimed",
+      "name": "closeEntrant",
+      "docs": [
+        "Returns an entrant's rent to its owner once the table is final and its chip",
+        "positions are claimed. Anyone can call it; `close_tournament` waits for all of them."
+      ],
       "discriminator": [
-        217,
-        192,
-        123,
-        72,
+        229,
+        9,
         108,
-        150,
-        248,
-        33
-      ]
+        179,
+        29,
+        31,
+        174,
+        200
+      ],
+      "accounts": [
+        {
+          "name": "tournament",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  116,
+                  111,
+                  117,
+                  114,
+                  110,
+                  97,
+                  109,
+                  101,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "tournament.tournament_id",
+                "account": "tournament"
+              }
+            ]
+          }
+        },
+        {
+          "name": "entrant",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  110,
+                  116,
+                  114,
+                  97,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "tournament"
+              },
+              {
+                "kind": "account",
+                "path": "entrant.user",
+                "account": "entrant"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "writable": true
+        }
+      ],
+      "args": []
     },
     {
-      "name": "roundCreated",
+      "name": "closeOffer",
+      "docs": [
+        "Returns the unfilled part of an offer to the maker once the round has locked."
+      ],
       "discriminator": [
-        16,
-        19,
-        68,
-        117,
-        87,
-        198,
-        7,
-        124
-      ]
+        191,
+        72,
+        67,
+        35,
+        239,
+        209,
+        97,
+        132
+      ],
+      "accounts": [
+        {
+          "name": "round",
+          "writable": true
+        },
+        {
+          "name": "offer",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  102,
+                  102,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "offer.round",
+                "account": "offer"
+              },
+              {
+                "kind": "account",
+                "path": "offer.maker",
+                "account": "offer"
+              },
+              {
+                "kind": "account",
+                "path": "offer.offer_id",
+                "account": "offer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "maker",
+          "writable": true,
+          "relations": [
+            "offer"
+          ]
+        }
+      ],
+      "args": []
     },
     {
-      "name": "roundJoined",
+      "name": "closePosition",
+      "docs": [
+        "The owner closes a claimed or losing position; once the claim window is over",
+        "anyone can close any position, returning the rent to `position.user`, so",
+        "abandoned positions cannot hold up `close_round`."
+      ],
       "discriminator": [
-        106,
-        227,
-        27,
-        229,
         123,
-        51,
-        104,
-        141
-      ]
-    },
-    {
-      "name": "roundLocked",
-      "discriminator": [
-        19,
-        58,
-        91,
-        157,
-        24,
-        76,
-        207,
-        7
-      ]
-    },
-    {
-      "name": "roundSettled",
-      "discriminator": [
-        249,
-        225,
-        66,
-        54,
-        157,
-        200,
-        234,
-        222
-      ]
-    }
-  ],
-  "errors": [
-    {
+        134,
+        81,
+        0,
+        49,
+        68,
+        98,
+        98
+      ],
+      "accounts": [
+        {
+          "name": "closer",
+          "signer": true
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "round",
+          "writable": true
+        },
+        {
+          "name": "position",
+          "writable": true
+        },
+        {
+          "name": "user",
+          "writable": true
+        },
+        {
+          "name": "userStats",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "position.user",
+                "account": "position"
+              }
+            ]
+          }
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "closeRound",
+      "discriminator": [
+        149,
+        14,
+        81,
+        88,
+        230,
+        226,
+        234,
+        37
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "round",
+          "writable": true
+        },
+        {
+          "name": "upVault",
+          "writable": true
+        },
+        {
+          "name": "downVault",
+          "writable": true
+        },
+        {
+          "name": "treasury",
+          "writable": true
+        },
+        {
+          "name": "rentPayer",
+          "writable": true
+        },
+        {
+          "name": "insuranceFund",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  105,
+                  110,
+                  115,
+                  117,
+                  114,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "closeSeason",
+      "docs": [
+        "Sweeps unclaimed and unallocated prizes to the treasury with the account rent."
+      ],
+      "discriminator": [
+        162,
+        198,
+        31,
+        37,
+        77,
+        0,
+        199,
+        152
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "season",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  97,
+                  115,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "season.season_id",
+                "account": "season"
+              }
+            ]
+          }
+        },
+        {
+          "name": "treasury",
+          "writable": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "closeTournament",
+      "docs": [
+        "Sweeps unclaimed and unallocated prizes to the treasury with the account rent."
+      ],
+      "discriminator": [
+        14,
+        80,
+        54,
+        9,
+        221,
+        239,
+        201,
+        35
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "tournament",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  116,
+                  111,
+                  117,
+                  114,
+                  110,
+                  97,
+                  109,
+                  101,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "tournament.tournament_id",
+                "account": "tournament"
+              }
+            ]
+          }
+        },
+        {
+          "name": "treasury",
+          "writable": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "commitStake",
+      "docs": [
+        "Commit-reveal rounds take `hash(side || salt || user)` with the stake before the",
+        "entry cutoff; the stake waits in the commitment PDA until it is revealed."
+      ],
+      "discriminator": [
+        119,
+        23,
+        192,
+        201,
+        109,
+        236,
+        148,
+        232
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "round",
+          "writable": true
+        },
+        {
+          "name": "commitment",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  109,
+                  109,
+                  105,
+                  116,
+                  109,
+                  101,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "round"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "commitmentHash",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "lamports",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "createDuel",
+      "docs": [
+        "Opens a 1v1 duel and escrows the challenger's stake in the duel PDA."
+      ],
+      "discriminator": [
+        49,
+        28,
+        93,
+        11,
+        75,
+        242,
+        69,
+        165
+      ],
+      "accounts": [
+        {
+          "name": "challenger",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "duel",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  117,
+                  101,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "challenger"
+              },
+              {
+                "kind": "arg",
+                "path": "duelId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "duelId",
+          "type": "u64"
+        },
+        {
+          "name": "market",
+          "type": "u8"
+        },
+        {
+          "name": "side",
+          "type": "u8"
+        },
+        {
+          "name": "stakeLamports",
+          "type": "u64"
+        },
+        {
+          "name": "expiryTs",
+          "type": "i64"
+        },
+        {
+          "name": "durationSeconds",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "createRoom",
+      "discriminator": [
+        130,
+        166,
+        32,
+        2,
+        247,
+        120,
+        178,
+        53
+      ],
+      "accounts": [
+        {
+          "name": "host",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "room",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  111,
+                  111,
+                  109
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "host"
+              },
+              {
+                "kind": "arg",
+                "path": "roomId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "roomId",
+          "type": "u32"
+        },
+        {
+          "name": "allowlistRoot",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "stakeLamports",
+          "type": "u64"
+        },
+        {
+          "name": "hostFeeBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "createRoomRound",
+      "docs": [
+        "Host-created round on one of the regular markets. Joins need an allowlist proof",
+        "against the room's merkle root, and the host takes `host_fee_bps` at settlement."
+      ],
+      "discriminator": [
+        62,
+        162,
+        146,
+        67,
+        84,
+        124,
+        214,
+        230
+      ],
+      "accounts": [
+        {
+          "name": "host",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "room"
+          ]
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "room",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  111,
+                  111,
+                  109
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "host"
+              },
+              {
+                "kind": "account",
+                "path": "room.room_id",
+                "account": "room"
+              }
+            ]
+          }
+        },
+        {
+          "name": "round",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  111,
+                  111,
+                  109,
+                  95,
+                  114,
+                  111,
+                  117,
+                  110,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "room"
+              },
+              {
+                "kind": "arg",
+                "path": "roundId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "upVault",
+          "writable": true
+        },
+        {
+          "name": "downVault",
+          "writable": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "market",
+          "type": "u8"
+        },
+        {
+          "name": "roundId",
+          "type": "i64"
+        },
+        {
+          "name": "lockTs",
+          "type": "i64"
+        },
+        {
+          "name": "endTs",
+          "type": "i64"
+        },
+        {
+          "name": "feedId",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "oraclePriceAccount",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "createRound",
+      "discriminator": [
+        229,
+        218,
+        236,
+        169,
+        231,
+        80,
+        134,
+        112
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "round",
+          "writable": true
+        },
+        {
+          "name": "upVault",
+          "writable": true
+        },
+        {
+          "name": "downVault",
+          "writable": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "market",
+          "type": "u8"
+        },
+        {
+          "name": "roundId",
+          "type": "i64"
+        },
+        {
+          "name": "lockTs",
+          "type": "i64"
+        },
+        {
+          "name": "endTs",
+          "type": "i64"
+        },
+        {
+          "name": "feedId",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "oraclePriceAccount",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "createSeason",
+      "discriminator": [
+        38,
+        108,
+        29,
+        127,
+        60,
+        126,
+        101,
+        3
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        },
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "season",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  97,
+                  115,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "seasonId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "currentSeason",
+          "docs": [
+            "The currently active season, required while one is set."
+          ],
+          "optional": true
+        }
+      ],
+      "args": [
+        {
+          "name": "seasonId",
+          "type": "u32"
+        },
+        {
+          "name": "startTs",
+          "type": "i64"
+        },
+        {
+          "name": "endTs",
+          "type": "i64"
+        },
+        {
+          "name": "prizeBps",
+          "type": {
+            "vec": "u16"
+          }
+        }
+      ]
+    },
+    {
+      "name": "createTournament",
+      "discriminator": [
+        158,
+        137,
+        233,
+        231,
+        73,
+        132,
+        191,
+        68
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "tournament",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  116,
+                  111,
+                  117,
+                  114,
+                  110,
+                  97,
+                  109,
+                  101,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "tournamentId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "tournamentId",
+          "type": "u32"
+        },
+        {
+          "name": "entryFeeLamports",
+          "type": "u64"
+        },
+        {
+          "name": "startingChips",
+          "type": "u64"
+        },
+        {
+          "name": "marketsMask",
+          "type": "u8"
+        },
+        {
+          "name": "startTs",
+          "type": "i64"
+        },
+        {
+          "name": "endTs",
+          "type": "i64"
+        },
+        {
+          "name": "prizeBps",
+          "type": {
+            "vec": "u16"
+          }
+        }
+      ]
+    },
+    {
+      "name": "enterTournament",
+      "discriminator": [
+        19,
+        21,
+        109,
+        109,
+        227,
+        108,
+        232,
+        25
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tournament",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  116,
+                  111,
+                  117,
+                  114,
+                  110,
+                  97,
+                  109,
+                  101,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "tournament.tournament_id",
+                "account": "tournament"
+              }
+            ]
+          }
+        },
+        {
+          "name": "entrant",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  110,
+                  116,
+                  114,
+                  97,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "tournament"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "fillOffer",
+      "docs": [
+        "Fills an offer with `lamports` of taker stake. The stake and the matching slice of",
+        "maker liability move into a new `Contract` PDA."
+      ],
+      "discriminator": [
+        83,
+        15,
+        200,
+        85,
+        160,
+        80,
+        164,
+        61
+      ],
+      "accounts": [
+        {
+          "name": "taker",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "round",
+          "writable": true
+        },
+        {
+          "name": "offer",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  102,
+                  102,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "offer.round",
+                "account": "offer"
+              },
+              {
+                "kind": "account",
+                "path": "offer.maker",
+                "account": "offer"
+              },
+              {
+                "kind": "account",
+                "path": "offer.offer_id",
+                "account": "offer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "contract",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  116,
+                  114,
+                  97,
+                  99,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "offer"
+              },
+              {
+                "kind": "account",
+                "path": "offer.fill_count",
+                "account": "offer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "lamports",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "forfeitCommitment",
+      "docs": [
+        "Clears a commitment that was not revealed in its window: the stake goes to the",
+        "treasury when the round was created with `forfeit_unrevealed`, otherwise back to",
+        "the user less the protocol fee."
+      ],
+      "discriminator": [
+        211,
+        98,
+        44,
+        226,
+        160,
+        195,
+        45,
+        63
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "round",
+          "writable": true
+        },
+        {
+          "name": "commitment",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  109,
+                  109,
+                  105,
+                  116,
+                  109,
+                  101,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "round"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "relations": [
+            "commitment"
+          ]
+        },
+        {
+          "name": "treasury",
+          "writable": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "fundHouseVault",
+      "discriminator": [
+        232,
+        190,
+        239,
+        188,
+        79,
+        146,
+        90,
+        105
+      ],
+      "accounts": [
+        {
+          "name": "treasury",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "houseVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  111,
+                  117,
+                  115,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "lamports",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "fundSeason",
+      "discriminator": [
+        251,
+        186,
+        193,
+        242,
+        15,
+        144,
+        254,
+        67
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "season",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  97,
+                  115,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "season.season_id",
+                "account": "season"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "lamports",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "getUserStats",
+      "docs": [
+        "Read-only view for other programs: returns a snapshot of the user's stats,",
+        "including the rolling 30-day volume as of now, via return data."
+      ],
+      "discriminator": [
+        38,
+        55,
+        50,
+        132,
+        115,
+        127,
+        50,
+        32
+      ],
+      "accounts": [
+        {
+          "name": "userStats",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "user_stats.user",
+                "account": "userStats"
+              }
+            ]
+          }
+        }
+      ],
+      "args": [],
+      "returns": {
+        "defined": {
+          "name": "userStatsSnapshot"
+        }
+      }
+    },
+    {
+      "name": "initializeConfig",
+      "discriminator": [
+        208,
+        127,
+        21,
+        1,
+        194,
+        190,
+        196,
+        70
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true,
+          "address": "Dkm5UeGTaeXDkauBMtNwbHGw7q2aXbrqb9HBQVN5GFx8"
+        },
+        {
+          "name": "treasury"
+        },
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "feeBps",
+          "type": "u16"
+        },
+        {
+          "name": "oracleMaxAgeSec",
+          "type": "u32"
+        },
+        {
+          "name": "oracleProgram",
+          "type": "pubkey"
+        },
+        {
+          "name": "oracleAccountSol",
+          "type": "pubkey"
+        },
+        {
+          "name": "oracleAccountBtc",
+          "type": "pubkey"
+        },
+        {
+          "name": "oracleAccountEth",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "initializeHouseVault",
+      "discriminator": [
+        50,
+        40,
+        138,
+        92,
+        74,
+        81,
+        238,
+        139
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "houseVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  111,
+                  117,
+                  115,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "initializeInsuranceFund",
+      "discriminator": [
+        2,
+        239,
+        39,
+        87,
+        50,
+        28,
+        108,
+        12
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "insuranceFund",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  105,
+                  110,
+                  115,
+                  117,
+                  114,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "insurancePayout",
+      "discriminator": [
+        84,
+        182,
+        138,
+        42,
+        26,
+        137,
+        152,
+        33
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "insuranceFund",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  105,
+                  110,
+                  115,
+                  117,
+                  114,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "round",
+          "writable": true
+        },
+        {
+          "name": "position",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "recipient",
+          "writable": true
+        }
+      ],
+      "args": [
+        {
+          "name": "target",
+          "type": "u8"
+        },
+        {
+          "name": "lamports",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "joinMany",
+      "docs": [
+        "Joins several rounds in one instruction. `remaining_accounts` holds one",
+        "`(round, position, side_vault)` triple per leg, in the same order as `legs`."
+      ],
+      "discriminator": [
+        216,
+        254,
+        67,
+        56,
+        137,
+        56,
+        187,
+        171
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "userStats",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "season",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "seasonStats",
+          "writable": true,
+          "optional": true
+        }
+      ],
+      "args": [
+        {
+          "name": "legs",
+          "type": {
+            "vec": {
+              "defined": {
+                "name": "joinLeg"
+              }
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "joinRound",
+      "discriminator": [
+        191,
+        222,
+        86,
+        25,
+        234,
+        174,
+        157,
+        249
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "round",
+          "writable": true
+        },
+        {
+          "name": "position",
+          "writable": true
+        },
+        {
+          "name": "sideVault",
+          "writable": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "userStats",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "season",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "seasonStats",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "room",
+          "optional": true
+        }
+      ],
+      "args": [
+        {
+          "name": "side",
+          "type": "u8"
+        },
+        {
+          "name": "lamports",
+          "type": "u64"
+        },
+        {
+          "name": "minMultiplierBps",
+          "type": {
+            "option": "u64"
+          }
+        },
+        {
+          "name": "proof",
+          "type": {
+            "vec": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "joinRoundChips",
+      "discriminator": [
+        88,
+        241,
+        119,
+        71,
+        86,
+        225,
+        160,
+        167
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "tournament",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  116,
+                  111,
+                  117,
+                  114,
+                  110,
+                  97,
+                  109,
+                  101,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "tournament.tournament_id",
+                "account": "tournament"
+              }
+            ]
+          }
+        },
+        {
+          "name": "entrant",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  110,
+                  116,
+                  114,
+                  97,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "tournament"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "round",
+          "writable": true
+        },
+        {
+          "name": "chipPosition",
+          "writable": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "side",
+          "type": "u8"
+        },
+        {
+          "name": "chips",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "lockDuel",
+      "docs": [
+        "Permissionless: records the start price from an update published within",
+        "`ORACLE_PUBLISH_TOLERANCE_SECONDS` after acceptance."
+      ],
+      "discriminator": [
+        29,
+        244,
+        1,
+        124,
+        163,
+        135,
+        83,
+        168
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "duel",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  117,
+                  101,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "duel.challenger",
+                "account": "duel"
+              },
+              {
+                "kind": "account",
+                "path": "duel.duel_id",
+                "account": "duel"
+              }
+            ]
+          }
+        },
+        {
+          "name": "oraclePrice"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "lockRound",
+      "discriminator": [
+        68,
+        124,
+        43,
+        230,
+        30,
+        44,
+        248,
+        227
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "round",
+          "writable": true
+        },
+        {
+          "name": "oraclePrice"
+        },
+        {
+          "name": "houseVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  111,
+                  117,
+                  115,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "housePosition",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "houseSideVault",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "systemProgram",
+          "optional": true,
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateConfig",
+      "docs": [
+        "Grows a config written by an older build to the current layout. Appended fields",
+        "read as zero, apart from the claim window and the PriceUpdateV2 max age, which get",
+        "the `initialize_config` defaults. Safe to run again."
+      ],
+      "discriminator": [
+        92,
+        131,
+        58,
+        105,
+        210,
+        154,
+        224,
+        193
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "docs": [
+            "discriminator and admin are checked in the handler before it is resized"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "postOffer",
+      "docs": [
+        "Posts fixed odds on an open round: takers backing `side` are paid `odds_bps` of",
+        "their stake if it wins. `lamports` is the maker's escrowed liability."
+      ],
+      "discriminator": [
+        73,
+        150,
+        193,
+        114,
+        200,
+        133,
+        74,
+        58
+      ],
+      "accounts": [
+        {
+          "name": "maker",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "round",
+          "writable": true
+        },
+        {
+          "name": "offer",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  102,
+                  102,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "round"
+              },
+              {
+                "kind": "account",
+                "path": "maker"
+              },
+              {
+                "kind": "arg",
+                "path": "offerId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "offerId",
+          "type": "u64"
+        },
+        {
+          "name": "side",
+          "type": "u8"
+        },
+        {
+          "name": "oddsBps",
+          "type": "u32"
+        },
+        {
+          "name": "lamports",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "refundDuel",
+      "docs": [
+        "Permissionless: refunds both stakes of an accepted duel that was never locked, or",
+        "a locked duel that was never settled, once its oracle window has passed."
+      ],
+      "discriminator": [
+        102,
+        85,
+        18,
+        136,
+        100,
+        103,
+        76,
+        189
+      ],
+      "accounts": [
+        {
+          "name": "duel",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  117,
+                  101,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "duel.challenger",
+                "account": "duel"
+              },
+              {
+                "kind": "account",
+                "path": "duel.duel_id",
+                "account": "duel"
+              }
+            ]
+          }
+        },
+        {
+          "name": "challenger",
+          "writable": true,
+          "relations": [
+            "duel"
+          ]
+        },
+        {
+          "name": "counterparty",
+          "writable": true,
+          "relations": [
+            "duel"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "revealStake",
+      "docs": [
+        "Reveals a commitment in the short window after `lock_ts`, booking the stake on its",
+        "side as if it had joined at commit time. The commitment PDA is closed to the user."
+      ],
+      "discriminator": [
+        107,
+        229,
+        210,
+        77,
+        126,
+        255,
+        243,
+        188
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "round",
+          "writable": true
+        },
+        {
+          "name": "commitment",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  109,
+                  109,
+                  105,
+                  116,
+                  109,
+                  101,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "round"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "position",
+          "writable": true
+        },
+        {
+          "name": "sideVault",
+          "writable": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "userStats",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "season",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "seasonStats",
+          "writable": true,
+          "optional": true
+        }
+      ],
+      "args": [
+        {
+          "name": "side",
+          "type": "u8"
+        },
+        {
+          "name": "salt",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "setActiveSeason",
+      "docs": [
+        "Points joins and claims at an existing season, or clears it when none is passed."
+      ],
+      "discriminator": [
+        94,
+        137,
+        10,
+        184,
+        214,
+        105,
+        72,
+        176
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        },
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "season",
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  97,
+                  115,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "season.season_id",
+                "account": "season"
+              }
+            ]
+          }
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setClaimWindow",
+      "discriminator": [
+        190,
+        124,
+        26,
+        181,
+        134,
+        231,
+        61,
+        190
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        },
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "claimWindowSeconds",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "setCommitReveal",
+      "discriminator": [
+        53,
+        8,
+        229,
+        223,
+        15,
+        231,
+        241,
+        132
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        },
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "commitRevealRounds",
+          "type": "bool"
+        },
+        {
+          "name": "forfeitUnrevealed",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "setConfig",
+      "discriminator": [
+        108,
+        158,
+        154,
+        175,
+        212,
+        98,
+        52,
+        66
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        },
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "feeBps",
+          "type": "u16"
+        },
+        {
+          "name": "oracleMaxAgeSec",
+          "type": "u32"
+        },
+        {
+          "name": "paused",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "setCrankTip",
+      "discriminator": [
+        10,
+        80,
+        20,
+        50,
+        163,
+        72,
+        193,
+        184
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        },
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "crankTipBps",
+          "type": "u16"
+        },
+        {
+          "name": "crankTipLamports",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setEntryCutoffs",
+      "discriminator": [
+        27,
+        213,
+        158,
+        145,
+        203,
+        152,
+        20,
+        239
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        },
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "entryCutoffSecondsSol",
+          "type": "u32"
+        },
+        {
+          "name": "entryCutoffSecondsBtc",
+          "type": "u32"
+        },
+        {
+          "name": "entryCutoffSecondsEth",
+          "type": "u32"
+        }
+      ]
+    },
+    {
+      "name": "setExitFee",
+      "discriminator": [
+        73,
+        233,
+        248,
+        83,
+        22,
+        236,
+        87,
+        93
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        },
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "exitFeeBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "setFeeSplit",
+      "discriminator": [
+        248,
+        186,
+        180,
+        130,
+        109,
+        11,
+        93,
+        203
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        },
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "recipients",
+          "type": {
+            "vec": "pubkey"
+          }
+        },
+        {
+          "name": "recipientBps",
+          "type": {
+            "vec": "u16"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setFeeTiers",
+      "discriminator": [
+        162,
+        35,
+        72,
+        250,
+        39,
+        183,
+        30,
+        7
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        },
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "volumeThresholds",
+          "type": {
+            "vec": "u64"
+          }
+        },
+        {
+          "name": "tierBps",
+          "type": {
+            "vec": "u16"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setHouseConfig",
+      "discriminator": [
+        221,
+        12,
+        204,
+        232,
+        87,
+        127,
+        99,
+        83
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "houseVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  111,
+                  117,
+                  115,
+                  101
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "seedCapLamports",
+          "type": "u64"
+        },
+        {
+          "name": "enabled",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "setInsuranceConfig",
+      "discriminator": [
+        214,
+        146,
+        120,
+        78,
+        34,
+        232,
+        203,
+        21
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        },
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "insuranceFeeBps",
+          "type": "u16"
+        },
+        {
+          "name": "sweepToInsurance",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "setOracleAccounts",
+      "discriminator": [
+        174,
+        106,
+        146,
+        87,
+        199,
+        228,
+        200,
+        208
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        },
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "oracleAccountSol",
+          "type": "pubkey"
+        },
+        {
+          "name": "oracleAccountBtc",
+          "type": "pubkey"
+        },
+        {
+          "name": "oracleAccountEth",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "setOracleKind",
+      "docs": [
+        "Selects the legacy push account or the receiver's PriceUpdateV2 for one market.",
+        "Rounds keep the kind they were created with."
+      ],
+      "discriminator": [
+        250,
+        185,
+        76,
+        118,
+        127,
+        6,
+        124,
+        90
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        },
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "market",
+          "type": "u8"
+        },
+        {
+          "name": "oracleKind",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "setOracleMaxAgeSeconds",
+      "docs": [
+        "PriceUpdateV2 max age in seconds; `oracle_max_age_sec` stays the legacy slot age."
+      ],
+      "discriminator": [
+        222,
+        95,
+        224,
+        2,
+        66,
+        141,
+        185,
+        85
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        },
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "oracleMaxAgeSeconds",
+          "type": "u32"
+        }
+      ]
+    },
+    {
+      "name": "setParticipationRules",
+      "discriminator": [
+        171,
+        179,
+        82,
+        11,
+        168,
+        184,
+        39,
+        44
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        },
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "minSideParticipants",
+          "type": "u32"
+        },
+        {
+          "name": "minPoolLamports",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setReferralFee",
+      "discriminator": [
+        137,
+        113,
+        52,
+        190,
+        253,
+        2,
+        170,
+        109
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        },
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "referralFeeBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "setReferrer",
+      "discriminator": [
+        115,
+        251,
+        55,
+        0,
+        166,
+        189,
+        25,
+        74
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "referral",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  102,
+                  101,
+                  114,
+                  114,
+                  97,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "referrerRewards",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  102,
+                  101,
+                  114,
+                  114,
+                  101,
+                  114,
+                  95,
+                  114,
+                  101,
+                  119,
+                  97,
+                  114,
+                  100,
+                  115
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "referrer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "referrer",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "setStakeLimits",
+      "discriminator": [
+        10,
+        168,
+        57,
+        20,
+        114,
+        155,
+        250,
+        231
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        },
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "maxPositionLamports",
+          "type": "u64"
+        },
+        {
+          "name": "maxRoundLamports",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setSwitchFee",
+      "discriminator": [
+        85,
+        100,
+        127,
+        81,
+        154,
+        66,
+        140,
+        30
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        },
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "switchFeeBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "setTimeWeighting",
+      "discriminator": [
+        205,
+        65,
+        196,
+        95,
+        19,
+        234,
+        41,
+        22
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        },
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "timeWeightedRounds",
+          "type": "bool"
+        },
+        {
+          "name": "timeWeightFloorBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "setTreasury",
+      "discriminator": [
+        57,
+        97,
+        196,
+        95,
+        195,
+        206,
+        106,
+        136
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        },
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "newTreasury"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "settleContract",
+      "docs": [
+        "Pays out a fixed-odds contract from the round's lock and settle prices and closes",
+        "it to the taker. Contracts do not depend on the pool, so a round voided only for",
+        "participation still resolves them; an unlocked round or an unchanged price refunds",
+        "both legs."
+      ],
+      "discriminator": [
+        158,
+        177,
+        168,
+        232,
+        181,
+        0,
+        32,
+        220
+      ],
+      "accounts": [
+        {
+          "name": "round",
+          "writable": true
+        },
+        {
+          "name": "contract",
+          "writable": true
+        },
+        {
+          "name": "maker",
+          "writable": true,
+          "relations": [
+            "contract"
+          ]
+        },
+        {
+          "name": "taker",
+          "writable": true,
+          "relations": [
+            "contract"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "settleDuel",
+      "docs": [
+        "Settles a locked duel from the oracle and closes it to the challenger. The winner",
+        "takes both stakes minus `fee_bps`; an unchanged price refunds both sides fee-free."
+      ],
+      "discriminator": [
+        148,
+        90,
+        251,
+        130,
+        217,
+        144,
+        190,
+        239
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "duel",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  117,
+                  101,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "duel.challenger",
+                "account": "duel"
+              },
+              {
+                "kind": "account",
+                "path": "duel.duel_id",
+                "account": "duel"
+              }
+            ]
+          }
+        },
+        {
+          "name": "challenger",
+          "writable": true,
+          "relations": [
+            "duel"
+          ]
+        },
+        {
+          "name": "counterparty",
+          "writable": true,
+          "relations": [
+            "duel"
+          ]
+        },
+        {
+          "name": "oraclePrice"
+        },
+        {
+          "name": "treasury",
+          "writable": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "settleRound",
+      "discriminator": [
+        40,
+        101,
+        18,
+        1,
+        31,
+        129,
+        52,
+        77
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "round",
+          "writable": true
+        },
+        {
+          "name": "upVault",
+          "writable": true
+        },
+        {
+          "name": "downVault",
+          "writable": true
+        },
+        {
+          "name": "oraclePrice"
+        },
+        {
+          "name": "treasury",
+          "writable": true
+        },
+        {
+          "name": "roomHost",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "insuranceFund",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  105,
+                  110,
+                  115,
+                  117,
+                  114,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "submitScore",
+      "docs": [
+        "Ranks an entrant's chip balance after `end_ts`, including chips they won in",
+        "rounds they never claimed themselves."
+      ],
+      "discriminator": [
+        212,
+        128,
+        45,
+        22,
+        112,
+        82,
+        85,
+        235
+      ],
+      "accounts": [
+        {
+          "name": "tournament",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  116,
+                  111,
+                  117,
+                  114,
+                  110,
+                  97,
+                  109,
+                  101,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "tournament.tournament_id",
+                "account": "tournament"
+              }
+            ]
+          }
+        },
+        {
+          "name": "entrant",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  110,
+                  116,
+                  114,
+                  97,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "tournament"
+              },
+              {
+                "kind": "account",
+                "path": "entrant.user",
+                "account": "entrant"
+              }
+            ]
+          }
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "submitSeasonScore",
+      "docs": [
+        "Permissionless, so the final table reflects every player's season PnL rather than",
+        "only whoever traded last."
+      ],
+      "discriminator": [
+        161,
+        28,
+        65,
+        113,
+        120,
+        206,
+        170,
+        53
+      ],
+      "accounts": [
+        {
+          "name": "season",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  97,
+                  115,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "season.season_id",
+                "account": "season"
+              }
+            ]
+          }
+        },
+        {
+          "name": "seasonStats",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  97,
+                  115,
+                  111,
+                  110,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "season"
+              },
+              {
+                "kind": "account",
+                "path": "season_stats.user",
+                "account": "seasonStats"
+              }
+            ]
+          }
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "switchSide",
+      "discriminator": [
+        131,
+        149,
+        143,
+        194,
+        15,
+        107,
+        2,
+        67
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "round",
+          "writable": true
+        },
+        {
+          "name": "fromPosition",
+          "writable": true
+        },
+        {
+          "name": "toPosition",
+          "writable": true
+        },
+        {
+          "name": "fromVault",
+          "writable": true
+        },
+        {
+          "name": "toVault",
+          "writable": true
+        },
+        {
+          "name": "treasury",
+          "writable": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "userStats",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "toSide",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "withdrawHouseVault",
+      "discriminator": [
+        2,
+        5,
+        167,
+        75,
+        192,
+        103,
+        101,
+        25
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "houseVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  111,
+                  117,
+                  115,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "treasury",
+          "writable": true
+        }
+      ],
+      "args": [
+        {
+          "name": "lamports",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "withdrawPosition",
+      "discriminator": [
+        254,
+        30,
+        169,
+        94,
+        33,
+        171,
+        39,
+        104
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "round",
+          "writable": true
+        },
+        {
+          "name": "position",
+          "writable": true
+        },
+        {
+          "name": "sideVault",
+          "writable": true
+        },
+        {
+          "name": "treasury",
+          "writable": true
+        },
+        {
+          "name": "userStats",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "season",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "seasonStats",
+          "writable": true,
+          "optional": true
+        }
+      ],
+      "args": [
+        {
+          "name": "lamports",
+          "type": "u64"
+        }
+      ]
+    }
+  ],
+  "accounts": [
+    {
+      "name": "chipPosition",
+      "discriminator": [
+        116,
+        218,
+        187,
+        194,
+        222,
+        104,
+        220,
+        90
+      ]
+    },
+    {
+      "name": "commitment",
+      "discriminator": [
+        61,
+        112,
+        129,
+        128,
+        24,
+        147,
+        77,
+        87
+      ]
+    },
+    {
+      "name": "contract",
+      "discriminator": [
+        172,
+        138,
+        115,
+        242,
+        121,
+        67,
+        183,
+        26
+      ]
+    },
+    {
+      "name": "duel",
+      "discriminator": [
+        126,
+        229,
+        210,
+        60,
+        177,
+        135,
+        124,
+        224
+      ]
+    },
+    {
+      "name": "entrant",
+      "discriminator": [
+        64,
+        36,
+        220,
+        184,
+        151,
+        231,
+        35,
+        210
+      ]
+    },
+    {
+      "name": "globalConfig",
+      "discriminator": [
+        149,
+        8,
+        156,
+        202,
+        160,
+        252,
+        176,
+        217
+      ]
+    },
+    {
+      "name": "houseVault",
+      "discriminator": [
+        125,
+        17,
+        104,
+        101,
+        16,
+        34,
+        39,
+        135
+      ]
+    },
+    {
+      "name": "insuranceFund",
+      "discriminator": [
+        43,
+        134,
+        170,
+        87,
+        102,
+        16,
+        142,
+        147
+      ]
+    },
+    {
+      "name": "offer",
+      "discriminator": [
+        215,
+        88,
+        60,
+        71,
+        170,
+        162,
+        73,
+        229
+      ]
+    },
+    {
+      "name": "position",
+      "discriminator": [
+        170,
+        188,
+        143,
+        228,
+        122,
+        64,
+        247,
+        208
+      ]
+    },
+    {
+      "name": "referral",
+      "discriminator": [
+        30,
+        235,
+        136,
+        224,
+        106,
+        107,
+        49,
+        64
+      ]
+    },
+    {
+      "name": "referrerRewards",
+      "discriminator": [
+        106,
+        228,
+        24,
+        215,
+        100,
+        30,
+        75,
+        66
+      ]
+    },
+    {
+      "name": "room",
+      "discriminator": [
+        156,
+        199,
+        67,
+        27,
+        222,
+        23,
+        185,
+        94
+      ]
+    },
+    {
+      "name": "round",
+      "discriminator": [
+        87,
+        127,
+        165,
+        51,
+        73,
+        78,
+        116,
+        174
+      ]
+    },
+    {
+      "name": "season",
+      "discriminator": [
+        76,
+        67,
+        93,
+        156,
+        180,
+        157,
+        248,
+        47
+      ]
+    },
+    {
+      "name": "seasonStats",
+      "discriminator": [
+        23,
+        137,
+        84,
+        131,
+        148,
+        235,
+        209,
+        2
+      ]
+    },
+    {
+      "name": "tournament",
+      "discriminator": [
+        175,
+        139,
+        119,
+        242,
+        115,
+        194,
+        57,
+        92
+      ]
+    },
+    {
+      "name": "userStats",
+      "discriminator": [
+        176,
+        223,
+        136,
+        27,
+        122,
+        79,
+        32,
+        227
+      ]
+    },
+    {
+      "name": "vault",
+      "discriminator": [
+        211,
+        8,
+        232,
+        43,
+        2,
+        152,
+        117,
+        119
+      ]
+    }
+  ],
+  "events": [
+    {
+      "name": "chipsClaimed",
+      "discriminator": [
+        98,
+        9,
+        227,
+        123,
+        23,
+        147,
+        88,
+        54
+      ]
+    },
+    {
+      "name": "chipsJoined",
+      "discriminator": [
+        133,
+        75,
+        148,
+        101,
+        222,
+        200,
+        71,
+        62
+      ]
+    },
+    {
+      "name": "claimed",
+      "discriminator": [
+        217,
+        192,
+        123,
+        72,
+        108,
+        150,
+        248,
+        33
+      ]
+    },
+    {
+      "name": "commitmentForfeited",
+      "discriminator": [
+        255,
+        67,
+        147,
+        40,
+        3,
+        9,
+        81,
+        6
+      ]
+    },
+    {
+      "name": "contractSettled",
+      "discriminator": [
+        129,
+        107,
+        199,
+        124,
+        243,
+        145,
+        196,
+        72
+      ]
+    },
+    {
+      "name": "crankTipPaid",
+      "discriminator": [
+        174,
+        150,
+        153,
+        31,
+        72,
+        80,
+        216,
+        53
+      ]
+    },
+    {
+      "name": "duelAccepted",
+      "discriminator": [
+        237,
+        234,
+        86,
+        197,
+        103,
+        136,
+        209,
+        66
+      ]
+    },
+    {
+      "name": "duelCancelled",
+      "discriminator": [
+        138,
+        79,
+        20,
+        163,
+        207,
+        11,
+        111,
+        213
+      ]
+    },
+    {
+      "name": "duelCreated",
+      "discriminator": [
+        137,
+        77,
+        22,
+        196,
+        90,
+        147,
+        23,
+        37
+      ]
+    },
+    {
+      "name": "duelLocked",
+      "discriminator": [
+        24,
+        233,
+        58,
+        198,
+        28,
+        131,
+        37,
+        222
+      ]
+    },
+    {
+      "name": "duelRefunded",
+      "discriminator": [
+        164,
+        4,
+        2,
+        26,
+        73,
+        234,
+        56,
+        64
+      ]
+    },
+    {
+      "name": "duelSettled",
+      "discriminator": [
+        254,
+        160,
+        50,
+        193,
+        155,
+        112,
+        122,
+        64
+      ]
+    },
+    {
+      "name": "feeDistributed",
+      "discriminator": [
+        6,
+        133,
+        116,
+        50,
+        44,
+        151,
+        179,
+        65
+      ]
+    },
+    {
+      "name": "houseSeeded",
+      "discriminator": [
+        77,
+        172,
+        19,
+        218,
+        94,
+        9,
+        61,
+        237
+      ]
+    },
+    {
+      "name": "houseSettled",
+      "discriminator": [
+        197,
+        132,
+        89,
+        203,
+        181,
+        184,
+        5,
+        15
+      ]
+    },
+    {
+      "name": "houseVaultFunded",
+      "discriminator": [
+        197,
+        190,
+        126,
+        127,
+        51,
+        74,
+        135,
+        91
+      ]
+    },
+    {
+      "name": "houseVaultWithdrawn",
+      "discriminator": [
+        188,
+        93,
+        83,
+        62,
+        171,
+        44,
+        92,
+        188
+      ]
+    },
+    {
+      "name": "insuranceDeposited",
+      "discriminator": [
+        37,
+        242,
+        151,
+        250,
+        84,
+        199,
+        5,
+        77
+      ]
+    },
+    {
+      "name": "insurancePaidOut",
+      "discriminator": [
+        148,
+        7,
+        204,
+        203,
+        5,
+        130,
+        134,
+        89
+      ]
+    },
+    {
+      "name": "offerClosed",
+      "discriminator": [
+        237,
+        38,
+        102,
+        204,
+        165,
+        180,
+        177,
+        164
+      ]
+    },
+    {
+      "name": "offerFilled",
+      "discriminator": [
+        173,
+        104,
+        95,
+        161,
+        144,
+        206,
+        72,
+        57
+      ]
+    },
+    {
+      "name": "offerPosted",
+      "discriminator": [
+        213,
+        49,
+        239,
+        221,
+        100,
+        153,
+        210,
+        32
+      ]
+    },
+    {
+      "name": "positionClosed",
+      "discriminator": [
+        157,
+        163,
+        227,
+        228,
+        13,
+        97,
+        138,
+        121
+      ]
+    },
+    {
+      "name": "positionWithdrawn",
+      "discriminator": [
+        207,
+        105,
+        38,
+        76,
+        190,
+        32,
+        8,
+        81
+      ]
+    },
+    {
+      "name": "referralRewardAccrued",
+      "discriminator": [
+        35,
+        254,
+        111,
+        153,
+        235,
+        117,
+        73,
+        73
+      ]
+    },
+    {
+      "name": "referralRewardsClaimed",
+      "discriminator": [
+        178,
+        107,
+        76,
+        169,
+        252,
+        154,
+        45,
+        235
+      ]
+    },
+    {
+      "name": "referralSet",
+      "discriminator": [
+        39,
+        37,
+        242,
+        37,
+        60,
+        116,
+        243,
+        5
+      ]
+    },
+    {
+      "name": "roomCreated",
+      "discriminator": [
+        9,
+        177,
+        128,
+        166,
+        26,
+        19,
+        14,
+        243
+      ]
+    },
+    {
+      "name": "roomHostPaid",
+      "discriminator": [
+        214,
+        139,
+        151,
+        221,
+        209,
+        1,
+        39,
+        214
+      ]
+    },
+    {
+      "name": "roundClosed",
+      "discriminator": [
+        45,
+        243,
+        28,
+        22,
+        132,
+        70,
+        175,
+        226
+      ]
+    },
+    {
+      "name": "roundCreated",
+      "discriminator": [
+        16,
+        19,
+        68,
+        117,
+        87,
+        198,
+        7,
+        124
+      ]
+    },
+    {
+      "name": "roundJoined",
+      "discriminator": [
+        106,
+        227,
+        27,
+        229,
+        123,
+        51,
+        104,
+        141
+      ]
+    },
+    {
+      "name": "roundLocked",
+      "discriminator": [
+        19,
+        58,
+        91,
+        157,
+        24,
+        76,
+        207,
+        7
+      ]
+    },
+    {
+      "name": "roundSettled",
+      "discriminator": [
+        249,
+        225,
+        66,
+        54,
+        157,
+        200,
+        234,
+        222
+      ]
+    },
+    {
+      "name": "seasonClosed",
+      "discriminator": [
+        15,
+        224,
+        143,
+        189,
+        201,
+        13,
+        168,
+        149
+      ]
+    },
+    {
+      "name": "seasonCreated",
+      "discriminator": [
+        69,
+        129,
+        125,
+        162,
+        208,
+        173,
+        40,
+        68
+      ]
+    },
+    {
+      "name": "seasonPrizeClaimed",
+      "discriminator": [
+        135,
+        199,
+        106,
+        45,
+        156,
+        215,
+        207,
+        199
+      ]
+    },
+    {
+      "name": "sideSwitched",
+      "discriminator": [
+        12,
+        233,
+        133,
+        70,
+        173,
+        106,
+        244,
+        225
+      ]
+    },
+    {
+      "name": "stakeCommitted",
+      "discriminator": [
+        182,
+        84,
+        163,
+        152,
+        224,
+        21,
+        161,
+        147
+      ]
+    },
+    {
+      "name": "stakeRevealed",
+      "discriminator": [
+        123,
+        18,
+        61,
+        65,
+        218,
+        46,
+        154,
+        74
+      ]
+    },
+    {
+      "name": "tournamentClosed",
+      "discriminator": [
+        246,
+        137,
+        155,
+        89,
+        226,
+        38,
+        87,
+        8
+      ]
+    },
+    {
+      "name": "tournamentCreated",
+      "discriminator": [
+        102,
+        32,
+        240,
+        45,
+        52,
+        64,
+        97,
+        0
+      ]
+    },
+    {
+      "name": "tournamentEntered",
+      "discriminator": [
+        144,
+        158,
+        71,
+        217,
+        217,
+        6,
+        213,
+        15
+      ]
+    },
+    {
+      "name": "tournamentPrizeClaimed",
+      "discriminator": [
+        154,
+        237,
+        249,
+        4,
+        72,
+        239,
+        196,
+        101
+      ]
+    }
+  ],
+  "errors": [
+    {
       "code": 6000,
       "name": "invalidFeeBps",
       "msg": "Invalid fee bps"
     },
     {
-      "code": 6001,
-      "name": "protocolPaused",
-      "msg": "Protocol is paused"
+      "code": 6001,
+      "name": "protocolPaused",
+      "msg": "Protocol is paused"
+    },
+    {
+      "code": 6002,
+      "name": "invalidSchedule",
+      "msg": "Invalid schedule"
+    },
+    {
+      "code": 6003,
+      "name": "invalidSide",
+      "msg": "Invalid side"
+    },
+    {
+      "code": 6004,
+      "name": "invalidMarket",
+      "msg": "Invalid market"
+    },
+    {
+      "code": 6005,
+      "name": "invalidFeedId",
+      "msg": "Invalid feed id for market"
+    },
+    {
+      "code": 6006,
+      "name": "invalidStake",
+      "msg": "Invalid stake"
+    },
+    {
+      "code": 6007,
+      "name": "roundNotOpen",
+      "msg": "Round is not open"
+    },
+    {
+      "code": 6008,
+      "name": "roundLocked",
+      "msg": "Round is locked"
+    },
+    {
+      "code": 6009,
+      "name": "positionSideMismatch",
+      "msg": "Position side mismatch"
+    },
+    {
+      "code": 6010,
+      "name": "alreadyClaimed",
+      "msg": "Already claimed"
+    },
+    {
+      "code": 6011,
+      "name": "mathOverflow",
+      "msg": "Math overflow"
+    },
+    {
+      "code": 6012,
+      "name": "roundAlreadyLocked",
+      "msg": "Round already locked"
+    },
+    {
+      "code": 6013,
+      "name": "tooEarlyToLock",
+      "msg": "Too early to lock"
+    },
+    {
+      "code": 6014,
+      "name": "lockWindowExpired",
+      "msg": "Lock window expired"
+    },
+    {
+      "code": 6015,
+      "name": "tooEarlyToSettle",
+      "msg": "Too early to settle"
+    },
+    {
+      "code": 6016,
+      "name": "roundAlreadySettled",
+      "msg": "Round already settled"
+    },
+    {
+      "code": 6017,
+      "name": "insufficientVaultLiquidity",
+      "msg": "Insufficient vault liquidity"
+    },
+    {
+      "code": 6018,
+      "name": "roundNotSettled",
+      "msg": "Round not settled"
+    },
+    {
+      "code": 6019,
+      "name": "nothingToClaim",
+      "msg": "Nothing to claim"
+    },
+    {
+      "code": 6020,
+      "name": "vaultRoundMismatch",
+      "msg": "Vault round mismatch"
+    },
+    {
+      "code": 6021,
+      "name": "positionRoundMismatch",
+      "msg": "Position round mismatch"
+    },
+    {
+      "code": 6022,
+      "name": "positionUserMismatch",
+      "msg": "Position user mismatch"
+    },
+    {
+      "code": 6023,
+      "name": "invalidOraclePrice",
+      "msg": "Invalid oracle price update"
+    },
+    {
+      "code": 6024,
+      "name": "unexpectedOracleAccount",
+      "msg": "Unexpected oracle account"
+    },
+    {
+      "code": 6025,
+      "name": "invalidOracleOwner",
+      "msg": "Invalid oracle owner"
+    },
+    {
+      "code": 6026,
+      "name": "staleOraclePrice",
+      "msg": "Stale oracle price"
+    },
+    {
+      "code": 6027,
+      "name": "invalidBatch",
+      "msg": "Invalid batch accounts"
+    },
+    {
+      "code": 6028,
+      "name": "invalidPositionAccount",
+      "msg": "Invalid position account"
+    },
+    {
+      "code": 6029,
+      "name": "positionNotClaimed",
+      "msg": "Position has an unclaimed payout"
+    },
+    {
+      "code": 6030,
+      "name": "claimWindowOpen",
+      "msg": "Claim window still open"
+    },
+    {
+      "code": 6031,
+      "name": "positionCapExceeded",
+      "msg": "Position stake cap exceeded"
+    },
+    {
+      "code": 6032,
+      "name": "roundCapExceeded",
+      "msg": "Round pool cap exceeded"
+    },
+    {
+      "code": 6033,
+      "name": "oddsSlippageExceeded",
+      "msg": "Pool odds moved past slippage tolerance"
+    },
+    {
+      "code": 6034,
+      "name": "entryCutoffPassed",
+      "msg": "Entry cutoff passed"
+    },
+    {
+      "code": 6035,
+      "name": "invalidReferrer",
+      "msg": "Invalid referrer"
+    },
+    {
+      "code": 6036,
+      "name": "referralAccountsMismatch",
+      "msg": "Referral accounts mismatch"
+    },
+    {
+      "code": 6037,
+      "name": "invalidFeeSplit",
+      "msg": "Invalid fee split"
+    },
+    {
+      "code": 6038,
+      "name": "feeRecipientMismatch",
+      "msg": "Fee recipient mismatch"
+    },
+    {
+      "code": 6039,
+      "name": "insuranceFundRequired",
+      "msg": "Insurance fund account required"
+    },
+    {
+      "code": 6040,
+      "name": "invalidInsuranceTarget",
+      "msg": "Invalid insurance payout target"
+    },
+    {
+      "code": 6041,
+      "name": "houseAccountsRequired",
+      "msg": "House liquidity accounts required"
+    },
+    {
+      "code": 6042,
+      "name": "houseClaimRequired",
+      "msg": "House positions are claimed through claim_house"
+    },
+    {
+      "code": 6043,
+      "name": "invalidFeeTiers",
+      "msg": "Invalid fee tiers"
+    },
+    {
+      "code": 6044,
+      "name": "invalidSeason",
+      "msg": "Invalid season parameters"
+    },
+    {
+      "code": 6045,
+      "name": "seasonAccountsRequired",
+      "msg": "Active season accounts required"
+    },
+    {
+      "code": 6046,
+      "name": "seasonMismatch",
+      "msg": "Season account mismatch"
+    },
+    {
+      "code": 6047,
+      "name": "seasonFinalized",
+      "msg": "Season already finalized"
+    },
+    {
+      "code": 6048,
+      "name": "seasonNotFinalized",
+      "msg": "Season not finalized yet"
+    },
+    {
+      "code": 6049,
+      "name": "notSeasonWinner",
+      "msg": "No season prize for this user"
+    },
+    {
+      "code": 6050,
+      "name": "invalidTournament",
+      "msg": "Invalid tournament parameters"
+    },
+    {
+      "code": 6051,
+      "name": "tournamentRoundMismatch",
+      "msg": "Round is not part of this tournament"
+    },
+    {
+      "code": 6052,
+      "name": "insufficientChips",
+      "msg": "Not enough tournament chips"
+    },
+    {
+      "code": 6053,
+      "name": "tournamentFinalized",
+      "msg": "Tournament already finalized"
+    },
+    {
+      "code": 6054,
+      "name": "tournamentNotFinalized",
+      "msg": "Tournament not finalized yet"
+    },
+    {
+      "code": 6055,
+      "name": "invalidRoom",
+      "msg": "Invalid room parameters"
+    },
+    {
+      "code": 6056,
+      "name": "roomRequired",
+      "msg": "Room account required for room rounds"
+    },
+    {
+      "code": 6057,
+      "name": "roomMismatch",
+      "msg": "Room account mismatch"
+    },
+    {
+      "code": 6058,
+      "name": "notAllowlisted",
+      "msg": "Wallet is not on the room allowlist"
+    },
+    {
+      "code": 6059,
+      "name": "roomHostRequired",
+      "msg": "Room host account required"
+    },
+    {
+      "code": 6060,
+      "name": "duelNotOpen",
+      "msg": "Duel is not open"
+    },
+    {
+      "code": 6061,
+      "name": "duelNotLocked",
+      "msg": "Duel is not locked"
+    },
+    {
+      "code": 6062,
+      "name": "duelExpired",
+      "msg": "Duel offer expired"
+    },
+    {
+      "code": 6063,
+      "name": "duelNotExpired",
+      "msg": "Duel offer has not expired yet"
+    },
+    {
+      "code": 6064,
+      "name": "invalidDuelCounterparty",
+      "msg": "Challenger cannot accept their own duel"
+    },
+    {
+      "code": 6065,
+      "name": "invalidOdds",
+      "msg": "Invalid offer odds"
+    },
+    {
+      "code": 6066,
+      "name": "offerLiquidityExceeded",
+      "msg": "Fill exceeds the offer's remaining liquidity"
+    },
+    {
+      "code": 6067,
+      "name": "offerRoundMismatch",
+      "msg": "Offer round mismatch"
+    },
+    {
+      "code": 6068,
+      "name": "invalidOfferTaker",
+      "msg": "Makers cannot fill their own offer"
+    },
+    {
+      "code": 6069,
+      "name": "offerStillOpen",
+      "msg": "Offer can only be closed once the round locks"
+    },
+    {
+      "code": 6070,
+      "name": "commitRevealRound",
+      "msg": "Commit-reveal rounds take commit_stake instead of joins"
+    },
+    {
+      "code": 6071,
+      "name": "notCommitRevealRound",
+      "msg": "Round does not use commit-reveal"
+    },
+    {
+      "code": 6072,
+      "name": "invalidReveal",
+      "msg": "Reveal does not match the commitment"
+    },
+    {
+      "code": 6073,
+      "name": "revealWindowClosed",
+      "msg": "Reveal window is closed"
+    },
+    {
+      "code": 6074,
+      "name": "revealWindowOpen",
+      "msg": "Reveal window is still open"
+    },
+    {
+      "code": 6075,
+      "name": "invalidOracleKind",
+      "msg": "Invalid oracle kind"
+    },
+    {
+      "code": 6076,
+      "name": "insufficientOracleVerification",
+      "msg": "Oracle price update is not fully verified"
+    },
+    {
+      "code": 6077,
+      "name": "roundHasOpenAccounts",
+      "msg": "Round still has open positions"
+    },
+    {
+      "code": 6078,
+      "name": "feeRecipientNotRentExempt",
+      "msg": "Fee recipient is not rent-exempt"
+    },
+    {
+      "code": 6079,
+      "name": "insurancePayoutExceedsStake",
+      "msg": "Insurance payout exceeds what the position's claim falls short of its stake"
+    },
+    {
+      "code": 6080,
+      "name": "notPrizeWinner",
+      "msg": "No prize for this user"
+    },
+    {
+      "code": 6081,
+      "name": "seasonNotEnded",
+      "msg": "Season has not ended yet"
+    },
+    {
+      "code": 6082,
+      "name": "tournamentNotEnded",
+      "msg": "Tournament has not ended yet"
+    },
+    {
+      "code": 6083,
+      "name": "duelNotAccepted",
+      "msg": "Duel has not been accepted"
+    },
+    {
+      "code": 6084,
+      "name": "duelSettleWindowExpired",
+      "msg": "Duel settle window has expired"
+    },
+    {
+      "code": 6085,
+      "name": "duelNotRefundable",
+      "msg": "Duel cannot be refunded yet"
+    },
+    {
+      "code": 6086,
+      "name": "oraclePriceTooLate",
+      "msg": "Oracle price was published too late"
+    },
+    {
+      "code": 6087,
+      "name": "invalidOracleMaxAge",
+      "msg": "Oracle max age must be positive"
+    },
+    {
+      "code": 6088,
+      "name": "invalidConfigAccount",
+      "msg": "Config account is not a config owned by this admin"
+    },
+    {
+      "code": 6089,
+      "name": "entrantHasOpenPositions",
+      "msg": "Entrant still has unclaimed chip positions"
+    },
+    {
+      "code": 6090,
+      "name": "tournamentHasOpenEntrants",
+      "msg": "Tournament still has open entrants"
+    },
+    {
+      "code": 6091,
+      "name": "insuranceAfterClaims",
+      "msg": "Round pool cannot be topped up after claims"
+    }
+  ],
+  "types": [
+    {
+      "name": "chipPosition",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "round",
+            "type": "pubkey"
+          },
+          {
+            "name": "entrant",
+            "type": "pubkey"
+          },
+          {
+            "name": "side",
+            "type": "u8"
+          },
+          {
+            "name": "chips",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "chipsClaimed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "round",
+            "type": "pubkey"
+          },
+          {
+            "name": "tournament",
+            "type": "pubkey"
+          },
+          {
+            "name": "user",
+            "type": "pubkey"
+          },
+          {
+            "name": "side",
+            "type": "u8"
+          },
+          {
+            "name": "stake",
+            "type": "u64"
+          },
+          {
+            "name": "payout",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "chipsJoined",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "round",
+            "type": "pubkey"
+          },
+          {
+            "name": "tournament",
+            "type": "pubkey"
+          },
+          {
+            "name": "user",
+            "type": "pubkey"
+          },
+          {
+            "name": "side",
+            "type": "u8"
+          },
+          {
+            "name": "chips",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "claimed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "round",
+            "type": "pubkey"
+          },
+          {
+            "name": "user",
+            "type": "pubkey"
+          },
+          {
+            "name": "side",
+            "type": "u8"
+          },
+          {
+            "name": "stake",
+            "type": "u64"
+          },
+          {
+            "name": "payout",
+            "type": "u64"
+          },
+          {
+            "name": "fee",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "commitment",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "round",
+            "type": "pubkey"
+          },
+          {
+            "name": "user",
+            "type": "pubkey"
+          },
+          {
+            "name": "commitmentHash",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "lamports",
+            "type": "u64"
+          },
+          {
+            "name": "committedTs",
+            "type": "i64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "commitmentForfeited",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "round",
+            "type": "pubkey"
+          },
+          {
+            "name": "user",
+            "type": "pubkey"
+          },
+          {
+            "name": "lamports",
+            "type": "u64"
+          },
+          {
+            "name": "forfeited",
+            "type": "bool"
+          },
+          {
+            "name": "penaltyLamports",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "contract",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "offer",
+            "type": "pubkey"
+          },
+          {
+            "name": "round",
+            "type": "pubkey"
+          },
+          {
+            "name": "maker",
+            "type": "pubkey"
+          },
+          {
+            "name": "taker",
+            "type": "pubkey"
+          },
+          {
+            "name": "takerSide",
+            "type": "u8"
+          },
+          {
+            "name": "takerLamports",
+            "type": "u64"
+          },
+          {
+            "name": "makerLamports",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "contractSettled",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "contract",
+            "type": "pubkey"
+          },
+          {
+            "name": "round",
+            "type": "pubkey"
+          },
+          {
+            "name": "winnerSide",
+            "type": "u8"
+          },
+          {
+            "name": "takerPayout",
+            "type": "u64"
+          },
+          {
+            "name": "makerPayout",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "crankTipPaid",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "round",
+            "type": "pubkey"
+          },
+          {
+            "name": "user",
+            "type": "pubkey"
+          },
+          {
+            "name": "cranker",
+            "type": "pubkey"
+          },
+          {
+            "name": "tipLamports",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "duel",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "challenger",
+            "type": "pubkey"
+          },
+          {
+            "name": "counterparty",
+            "type": "pubkey"
+          },
+          {
+            "name": "duelId",
+            "type": "u64"
+          },
+          {
+            "name": "market",
+            "type": "u8"
+          },
+          {
+            "name": "challengerSide",
+            "type": "u8"
+          },
+          {
+            "name": "stakeLamports",
+            "type": "u64"
+          },
+          {
+            "name": "expiryTs",
+            "type": "i64"
+          },
+          {
+            "name": "durationSeconds",
+            "type": "i64"
+          },
+          {
+            "name": "status",
+            "type": "u8"
+          },
+          {
+            "name": "startPrice",
+            "type": "i64"
+          },
+          {
+            "name": "expo",
+            "type": "i32"
+          },
+          {
+            "name": "lockTs",
+            "type": "i64"
+          },
+          {
+            "name": "endTs",
+            "type": "i64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "oracleKind",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "duelAccepted",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "duel",
+            "type": "pubkey"
+          },
+          {
+            "name": "counterparty",
+            "type": "pubkey"
+          },
+          {
+            "name": "lockTs",
+            "type": "i64"
+          },
+          {
+            "name": "endTs",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "duelCancelled",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "duel",
+            "type": "pubkey"
+          },
+          {
+            "name": "challenger",
+            "type": "pubkey"
+          },
+          {
+            "name": "refundedLamports",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "duelCreated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "duel",
+            "type": "pubkey"
+          },
+          {
+            "name": "challenger",
+            "type": "pubkey"
+          },
+          {
+            "name": "market",
+            "type": "u8"
+          },
+          {
+            "name": "side",
+            "type": "u8"
+          },
+          {
+            "name": "stakeLamports",
+            "type": "u64"
+          },
+          {
+            "name": "expiryTs",
+            "type": "i64"
+          },
+          {
+            "name": "durationSeconds",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "duelLocked",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "duel",
+            "type": "pubkey"
+          },
+          {
+            "name": "startPrice",
+            "type": "i64"
+          },
+          {
+            "name": "expo",
+            "type": "i32"
+          },
+          {
+            "name": "endTs",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "duelRefunded",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "duel",
+            "type": "pubkey"
+          },
+          {
+            "name": "challenger",
+            "type": "pubkey"
+          },
+          {
+            "name": "counterparty",
+            "type": "pubkey"
+          },
+          {
+            "name": "refundedLamports",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "duelSettled",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "duel",
+            "type": "pubkey"
+          },
+          {
+            "name": "winner",
+            "type": "pubkey"
+          },
+          {
+            "name": "startPrice",
+            "type": "i64"
+          },
+          {
+            "name": "endPrice",
+            "type": "i64"
+          },
+          {
+            "name": "payout",
+            "type": "u64"
+          },
+          {
+            "name": "fee",
+            "type": "u64"
+          },
+          {
+            "name": "settledAt",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "entrant",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tournament",
+            "type": "pubkey"
+          },
+          {
+            "name": "user",
+            "type": "pubkey"
+          },
+          {
+            "name": "chips",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "openChipPositions",
+            "type": "u32"
+          }
+        ]
+      }
+    },
+    {
+      "name": "feeDistributed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "round",
+            "type": "pubkey"
+          },
+          {
+            "name": "recipient",
+            "type": "pubkey"
+          },
+          {
+            "name": "lamports",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "globalConfig",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "type": "pubkey"
+          },
+          {
+            "name": "treasury",
+            "type": "pubkey"
+          },
+          {
+            "name": "oracleProgram",
+            "type": "pubkey"
+          },
+          {
+            "name": "oracleAccountSol",
+            "type": "pubkey"
+          },
+          {
+            "name": "oracleAccountBtc",
+            "type": "pubkey"
+          },
+          {
+            "name": "oracleAccountEth",
+            "type": "pubkey"
+          },
+          {
+            "name": "feeBps",
+            "type": "u16"
+          },
+          {
+            "name": "oracleMaxAgeSec",
+            "type": "u32"
+          },
+          {
+            "name": "paused",
+            "type": "bool"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "crankTipBps",
+            "type": "u16"
+          },
+          {
+            "name": "crankTipLamports",
+            "type": "u64"
+          },
+          {
+            "name": "claimWindowSeconds",
+            "type": "i64"
+          },
+          {
+            "name": "exitFeeBps",
+            "type": "u16"
+          },
+          {
+            "name": "switchFeeBps",
+            "type": "u16"
+          },
+          {
+            "name": "maxPositionLamports",
+            "type": "u64"
+          },
+          {
+            "name": "maxRoundLamports",
+            "type": "u64"
+          },
+          {
+            "name": "minSideParticipants",
+            "type": "u32"
+          },
+          {
+            "name": "minPoolLamports",
+            "type": "u64"
+          },
+          {
+            "name": "entryCutoffSecondsSol",
+            "type": "u32"
+          },
+          {
+            "name": "entryCutoffSecondsBtc",
+            "type": "u32"
+          },
+          {
+            "name": "entryCutoffSecondsEth",
+            "type": "u32"
+          },
+          {
+            "name": "timeWeightedRounds",
+            "type": "bool"
+          },
+          {
+            "name": "timeWeightFloorBps",
+            "type": "u16"
+          },
+          {
+            "name": "referralFeeBps",
+            "type": "u16"
+          },
+          {
+            "name": "feeSplitCount",
+            "type": "u8"
+          },
+          {
+            "name": "feeSplitRecipients",
+            "type": {
+              "array": [
+                "pubkey",
+                5
+              ]
+            }
+          },
+          {
+            "name": "feeSplitBps",
+            "type": {
+              "array": [
+                "u16",
+                5
+              ]
+            }
+          },
+          {
+            "name": "insuranceFeeBps",
+            "type": "u16"
+          },
+          {
+            "name": "sweepToInsurance",
+            "type": "bool"
+          },
+          {
+            "name": "feeTierCount",
+            "type": "u8"
+          },
+          {
+            "name": "feeTierVolumeLamports",
+            "type": {
+              "array": [
+                "u64",
+                5
+              ]
+            }
+          },
+          {
+            "name": "feeTierBps",
+            "type": {
+              "array": [
+                "u16",
+                5
+              ]
+            }
+          },
+          {
+            "name": "activeSeason",
+            "type": "pubkey"
+          },
+          {
+            "name": "commitRevealRounds",
+            "type": "bool"
+          },
+          {
+            "name": "forfeitUnrevealed",
+            "type": "bool"
+          },
+          {
+            "name": "oracleKinds",
+            "type": {
+              "array": [
+                "u8",
+                3
+              ]
+            }
+          },
+          {
+            "name": "oracleMaxAgeSeconds",
+            "type": "u32"
+          }
+        ]
+      }
+    },
+    {
+      "name": "houseSeeded",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "round",
+            "type": "pubkey"
+          },
+          {
+            "name": "market",
+            "type": "u8"
+          },
+          {
+            "name": "side",
+            "type": "u8"
+          },
+          {
+            "name": "lamports",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "houseSettled",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "round",
+            "type": "pubkey"
+          },
+          {
+            "name": "market",
+            "type": "u8"
+          },
+          {
+            "name": "stake",
+            "type": "u64"
+          },
+          {
+            "name": "payout",
+            "type": "u64"
+          },
+          {
+            "name": "marketPnlLamports",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "houseVault",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "enabled",
+            "type": "bool"
+          },
+          {
+            "name": "seedCapLamports",
+            "type": "u64"
+          },
+          {
+            "name": "totalFundedLamports",
+            "type": "u64"
+          },
+          {
+            "name": "totalWithdrawnLamports",
+            "type": "u64"
+          },
+          {
+            "name": "seededLamports",
+            "type": {
+              "array": [
+                "u64",
+                3
+              ]
+            }
+          },
+          {
+            "name": "returnedLamports",
+            "type": {
+              "array": [
+                "u64",
+                3
+              ]
+            }
+          },
+          {
+            "name": "pnlLamports",
+            "type": {
+              "array": [
+                "i64",
+                3
+              ]
+            }
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "houseVaultFunded",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "lamports",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "houseVaultWithdrawn",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "lamports",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "insuranceDeposited",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "round",
+            "type": "pubkey"
+          },
+          {
+            "name": "lamports",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "insuranceFund",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "totalDepositedLamports",
+            "type": "u64"
+          },
+          {
+            "name": "totalPaidOutLamports",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "insurancePaidOut",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "round",
+            "type": "pubkey"
+          },
+          {
+            "name": "recipient",
+            "type": "pubkey"
+          },
+          {
+            "name": "target",
+            "type": "u8"
+          },
+          {
+            "name": "lamports",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "joinLeg",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "side",
+            "type": "u8"
+          },
+          {
+            "name": "lamports",
+            "type": "u64"
+          },
+          {
+            "name": "minMultiplierBps",
+            "type": {
+              "option": "u64"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "offer",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "round",
+            "type": "pubkey"
+          },
+          {
+            "name": "maker",
+            "type": "pubkey"
+          },
+          {
+            "name": "offerId",
+            "type": "u64"
+          },
+          {
+            "name": "side",
+            "type": "u8"
+          },
+          {
+            "name": "oddsBps",
+            "type": "u32"
+          },
+          {
+            "name": "makerLamports",
+            "type": "u64"
+          },
+          {
+            "name": "remainingLamports",
+            "type": "u64"
+          },
+          {
+            "name": "fillCount",
+            "type": "u32"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "offerClosed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "offer",
+            "type": "pubkey"
+          },
+          {
+            "name": "maker",
+            "type": "pubkey"
+          },
+          {
+            "name": "refundedLamports",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "offerFilled",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "offer",
+            "type": "pubkey"
+          },
+          {
+            "name": "contract",
+            "type": "pubkey"
+          },
+          {
+            "name": "taker",
+            "type": "pubkey"
+          },
+          {
+            "name": "takerLamports",
+            "type": "u64"
+          },
+          {
+            "name": "makerLamports",
+            "type": "u64"
+          },
+          {
+            "name": "remainingLamports",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "offerPosted",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "offer",
+            "type": "pubkey"
+          },
+          {
+            "name": "round",
+            "type": "pubkey"
+          },
+          {
+            "name": "maker",
+            "type": "pubkey"
+          },
+          {
+            "name": "side",
+            "type": "u8"
+          },
+          {
+            "name": "oddsBps",
+            "type": "u32"
+          },
+          {
+            "name": "lamports",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "position",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "round",
+            "type": "pubkey"
+          },
+          {
+            "name": "user",
+            "type": "pubkey"
+          },
+          {
+            "name": "side",
+            "type": "u8"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "claimed",
+            "type": "bool"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "weight",
+            "type": "u64"
+          },
+          {
+            "name": "insuredLamports",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "positionClosed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "round",
+            "type": "pubkey"
+          },
+          {
+            "name": "user",
+            "type": "pubkey"
+          },
+          {
+            "name": "side",
+            "type": "u8"
+          },
+          {
+            "name": "stake",
+            "type": "u64"
+          },
+          {
+            "name": "claimed",
+            "type": "bool"
+          },
+          {
+            "name": "rentLamports",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "positionWithdrawn",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "round",
+            "type": "pubkey"
+          },
+          {
+            "name": "user",
+            "type": "pubkey"
+          },
+          {
+            "name": "side",
+            "type": "u8"
+          },
+          {
+            "name": "lamports",
+            "type": "u64"
+          },
+          {
+            "name": "feeLamports",
+            "type": "u64"
+          },
+          {
+            "name": "remainingStake",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "rankEntry",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "user",
+            "type": "pubkey"
+          },
+          {
+            "name": "score",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "rankedTable",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "prizePoolLamports",
+            "type": "u64"
+          },
+          {
+            "name": "prizeBps",
+            "type": {
+              "array": [
+                "u16",
+                10
+              ]
+            }
+          },
+          {
+            "name": "entryCount",
+            "type": "u8"
+          },
+          {
+            "name": "entries",
+            "type": {
+              "array": [
+                {
+                  "defined": {
+                    "name": "rankEntry"
+                  }
+                },
+                10
+              ]
+            }
+          },
+          {
+            "name": "prizeClaimed",
+            "type": {
+              "array": [
+                "bool",
+                10
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "referral",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "user",
+            "type": "pubkey"
+          },
+          {
+            "name": "referrer",
+            "type": "pubkey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "referralRewardAccrued",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "round",
+            "type": "pubkey"
+          },
+          {
+            "name": "user",
+            "type": "pubkey"
+          },
+          {
+            "name": "referrer",
+            "type": "pubkey"
+          },
+          {
+            "name": "lamports",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "referralRewardsClaimed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "referrer",
+            "type": "pubkey"
+          },
+          {
+            "name": "lamports",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "referralSet",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "user",
+            "type": "pubkey"
+          },
+          {
+            "name": "referrer",
+            "type": "pubkey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "referrerRewards",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "referrer",
+            "type": "pubkey"
+          },
+          {
+            "name": "accruedLamports",
+            "type": "u64"
+          },
+          {
+            "name": "totalEarnedLamports",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "room",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "host",
+            "type": "pubkey"
+          },
+          {
+            "name": "roomId",
+            "type": "u32"
+          },
+          {
+            "name": "allowlistRoot",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "stakeLamports",
+            "type": "u64"
+          },
+          {
+            "name": "hostFeeBps",
+            "type": "u16"
+          },
+          {
+            "name": "roundCount",
+            "type": "u32"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
     },
     {
-      "code": 6002,
-      "name": "invalidSchedule",
-      "msg": "Invalid schedule"
+      "name": "roomCreated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "room",
+            "type": "pubkey"
+          },
+          {
+            "name": "host",
+            "type": "pubkey"
+          },
+          {
+            "name": "roomId",
+            "type": "u32"
+          },
+          {
+            "name": "stakeLamports",
+            "type": "u64"
+          },
+          {
+            "name": "hostFeeBps",
+            "type": "u16"
+          }
+        ]
+      }
     },
     {
-      "code": 6003,
-      "name": "invalidSide",
-      "msg": "Invalid side"
+      "name": "roomHostPaid",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "round",
+            "type": "pubkey"
+          },
+          {
+            "name": "room",
+            "type": "pubkey"
+          },
+          {
+            "name": "host",
+            "type": "pubkey"
+          },
+          {
+            "name": "lamports",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6004,
-      "name": "invalidMarket",
-      "msg": "Invalid market"
+      "name": "round",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "roundId",
+            "type": "i64"
+          },
+          {
+            "name": "market",
+            "type": "u8"
+          },
+          {
+            "name": "feedId",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "oraclePriceAccount",
+            "type": "pubkey"
+          },
+          {
+            "name": "lockTs",
+            "type": "i64"
+          },
+          {
+            "name": "endTs",
+            "type": "i64"
+          },
+          {
+            "name": "startPrice",
+            "type": "i64"
+          },
+          {
+            "name": "endPrice",
+            "type": "i64"
+          },
+          {
+            "name": "expo",
+            "type": "i32"
+          },
+          {
+            "name": "status",
+            "type": "u8"
+          },
+          {
+            "name": "winnerSide",
+            "type": "u8"
+          },
+          {
+            "name": "upTotal",
+            "type": "u64"
+          },
+          {
+            "name": "downTotal",
+            "type": "u64"
+          },
+          {
+            "name": "feeLamports",
+            "type": "u64"
+          },
+          {
+            "name": "distributableLamports",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "tipsPaidLamports",
+            "type": "u64"
+          },
+          {
+            "name": "settledTs",
+            "type": "i64"
+          },
+          {
+            "name": "rentPayer",
+            "type": "pubkey"
+          },
+          {
+            "name": "upCount",
+            "type": "u32"
+          },
+          {
+            "name": "downCount",
+            "type": "u32"
+          },
+          {
+            "name": "entryCutoffTs",
+            "type": "i64"
+          },
+          {
+            "name": "timeWeighted",
+            "type": "bool"
+          },
+          {
+            "name": "openTs",
+            "type": "i64"
+          },
+          {
+            "name": "timeWeightFloorBps",
+            "type": "u16"
+          },
+          {
+            "name": "upWeight",
+            "type": "u64"
+          },
+          {
+            "name": "downWeight",
+            "type": "u64"
+          },
+          {
+            "name": "referralPaidLamports",
+            "type": "u64"
+          },
+          {
+            "name": "upChips",
+            "type": "u64"
+          },
+          {
+            "name": "downChips",
+            "type": "u64"
+          },
+          {
+            "name": "room",
+            "type": "pubkey"
+          },
+          {
+            "name": "roomHost",
+            "type": "pubkey"
+          },
+          {
+            "name": "hostFeeBps",
+            "type": "u16"
+          },
+          {
+            "name": "hostFeeLamports",
+            "type": "u64"
+          },
+          {
+            "name": "commitReveal",
+            "type": "bool"
+          },
+          {
+            "name": "committedLamports",
+            "type": "u64"
+          },
+          {
+            "name": "revealedLamports",
+            "type": "u64"
+          },
+          {
+            "name": "oracleKind",
+            "type": "u8"
+          },
+          {
+            "name": "openAccounts",
+            "type": "u32"
+          },
+          {
+            "name": "settledFeeBps",
+            "type": "u16"
+          },
+          {
+            "name": "settledFeeLamports",
+            "type": "u64"
+          },
+          {
+            "name": "forfeitUnrevealed",
+            "type": "bool"
+          },
+          {
+            "name": "claimedPositions",
+            "type": "u32"
+          }
+        ]
+      }
     },
     {
-      "code": 6005,
-      "name": "invalidFeedId",
-      "msg": "Invalid feed id for market"
+      "name": "roundClosed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "round",
+            "type": "pubkey"
+          },
+          {
+            "name": "sweptLamports",
+            "type": "u64"
+          },
+          {
+            "name": "rentPayer",
+            "type": "pubkey"
+          },
+          {
+            "name": "closedAt",
+            "type": "i64"
+          },
+          {
+            "name": "protocolFeeLamports",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6006,
-      "name": "invalidStake",
-      "msg": "Invalid stake"
+      "name": "roundCreated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "round",
+            "type": "pubkey"
+          },
+          {
+            "name": "roundId",
+            "type": "i64"
+          },
+          {
+            "name": "market",
+            "type": "u8"
+          },
+          {
+            "name": "lockTs",
+            "type": "i64"
+          },
+          {
+            "name": "endTs",
+            "type": "i64"
+          },
+          {
+            "name": "entryCutoffTs",
+            "type": "i64"
+          }
+        ]
+      }
     },
     {
-      "code": 6007,
-      "name": "roundNotOpen",
-      "msg": "Round is not open"
+      "name": "roundJoined",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "round",
+            "type": "pubkey"
+          },
+          {
+            "name": "user",
+            "type": "pubkey"
+          },
+          {
+            "name": "side",
+            "type": "u8"
+          },
+          {
+            "name": "lamports",
+            "type": "u64"
+          },
+          {
+            "name": "weight",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6008,
       "name": "roundLocked",
-      "msg": "Round is locked"
-    },
-    {
-      "code": 6009,
-      "name": "positionSideMismatch",
-      "msg": "Position side mismatch"
-    },
-    {
-      "code": 6010,
-      "name": "alreadyClaimed",
-      "msg": "Already claimed"
-    },
-    {
-      "code": 6011,
-      "name": "mathOverflow",
-      "msg": "Math overflow"
-    },
-    {
-      "code": 6012,
-      "name": "roundAlreadyLocked",
-      "msg": "Round already locked"
-    },
-    {
-      "code": 6013,
-      "name": "tooEarlyToLock",
-      "msg": "Too early to lock"
-    },
-    {
-      "code": 6014,
-      "name": "lockWindowExpired",
-      "msg": "Lock window expired"
-    },
-    {
-      "code": 6015,
-      "name": "tooEarlyToSettle",
-      "msg": "Too early to settle"
-    },
-    {
-      "code": 6016,
-      "name": "roundAlreadySettled",
-      "msg": "Round already settled"
-    },
-    {
-      "code": 6017,
-      "name": "insufficientVaultLiquidity",
-      "msg": "Insufficient vault liquidity"
-    },
-    {
-      "code": 6018,
-      "name": "roundNotSettled",
-      "msg": "Round not settled"
-    },
-    {
-      "code": 6019,
-      "name": "nothingToClaim",
-      "msg": "Nothing to claim"
-    },
-    {
-      "code": 6020,
-      "name": "vaultRoundMismatch",
-      "msg": "Vault round mismatch"
-    },
-    {
-      "code": 6021,
-      "name": "positionRoundMismatch",
-      "msg": "Position round mismatch"
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "round",
+            "type": "pubkey"
+          },
+          {
+            "name": "startPrice",
+            "type": "i64"
+          },
+          {
+            "name": "expo",
+            "type": "i32"
+          },
+          {
+            "name": "lockedAt",
+            "type": "i64"
+          }
+        ]
+      }
     },
     {
-      "code": 6022,
-      "name": "positionUserMismatch",
-      "msg": "Position user mismatch"
+      "name": "roundSettled",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "round",
+            "type": "pubkey"
+          },
+          {
+            "name": "winnerSide",
+            "type": "u8"
+          },
+          {
+            "name": "startPrice",
+            "type": "i64"
+          },
+          {
+            "name": "endPrice",
+            "type": "i64"
+          },
+          {
+            "name": "feeLamports",
+            "type": "u64"
+          },
+          {
+            "name": "distributableLamports",
+            "type": "u64"
+          },
+          {
+            "name": "settledAt",
+            "type": "i64"
+          }
+        ]
+      }
     },
     {
-      "code": 6023,
-      "name": "invalidOraclePrice",
-      "msg": "Invalid oracle price update"
+      "name": "season",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "seasonId",
+            "type": "u32"
+          },
+          {
+            "name": "startTs",
+            "type": "i64"
+          },
+          {
+            "name": "endTs",
+            "type": "i64"
+          },
+          {
+            "name": "table",
+            "type": {
+              "defined": {
+                "name": "rankedTable"
+              }
+            }
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
     },
     {
-      "code": 6024,
-      "name": "unexpectedOracleAccount",
-      "msg": "Unexpected oracle account"
+      "name": "seasonClosed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "season",
+            "type": "pubkey"
+          },
+          {
+            "name": "sweptLamports",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6025,
-      "name": "invalidOracleOwner",
-      "msg": "Invalid oracle owner"
+      "name": "seasonCreated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "season",
+            "type": "pubkey"
+          },
+          {
+            "name": "seasonId",
+            "type": "u32"
+          },
+          {
+            "name": "startTs",
+            "type": "i64"
+          },
+          {
+            "name": "endTs",
+            "type": "i64"
+          }
+        ]
+      }
     },
     {
-      "code": 6026,
-      "name": "staleOraclePrice",
-      "msg": "Stale oracle price"
-    }
-  ],
-  "types": [
-    {
-      "name": "claimed",
+      "name": "seasonPrizeClaimed",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "round",
+            "name": "season",
             "type": "pubkey"
           },
           {
@@ -801,70 +8564,94 @@ export type PanchoPvp = {
             "type": "pubkey"
           },
           {
-            "name": "side",
+            "name": "rank",
             "type": "u8"
           },
           {
-            "name": "stake",
-            "type": "u64"
-          },
-          {
-            "name": "payout",
+            "name": "lamports",
             "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "globalConfig",
+      "name": "seasonStats",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "admin",
+            "name": "season",
             "type": "pubkey"
           },
           {
-            "name": "treasury",
+            "name": "user",
             "type": "pubkey"
           },
           {
-            "name": "oracleProgram",
-            "type": "pubkey"
+            "name": "pnlLamports",
+            "type": "i64"
           },
           {
-            "name": "oracleAccountSol",
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "sideSwitched",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "round",
             "type": "pubkey"
           },
           {
-            "name": "oracleAccountBtc",
+            "name": "user",
             "type": "pubkey"
           },
           {
-            "name": "oracleAccountEth",
-            "type": "pubkey"
+            "name": "fromSide",
+            "type": "u8"
           },
           {
-            "name": "feeBps",
-            "type": "u16"
+            "name": "toSide",
+            "type": "u8"
           },
           {
-            "name": "oracleMaxAgeSec",
-            "type": "u32"
+            "name": "lamports",
+            "type": "u64"
           },
           {
-            "name": "paused",
-            "type": "bool"
+            "name": "feeLamports",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "stakeCommitted",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "round",
+            "type": "pubkey"
           },
           {
-            "name": "bump",
-            "type": "u8"
+            "name": "user",
+            "type": "pubkey"
+          },
+          {
+            "name": "lamports",
+            "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "position",
+      "name": "stakeRevealed",
       "type": {
         "kind": "struct",
         "fields": [
@@ -881,48 +8668,39 @@ export type PanchoPvp = {
             "type": "u8"
           },
           {
-            "name": "amount",
+            "name": "lamports",
             "type": "u64"
           },
           {
-            "name": "claimed",
-            "type": "bool"
-          },
-          {
-            "name": "bump",
-            "type": "u8"
+            "name": "weight",
+            "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "round",
+      "name": "tournament",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "roundId",
-            "type": "i64"
+            "name": "tournamentId",
+            "type": "u32"
           },
           {
-            "name": "market",
-            "type": "u8"
+            "name": "entryFeeLamports",
+            "type": "u64"
           },
           {
-            "name": "feedId",
-            "type": {
-              "array": [
-                "u8",
-                32
-              ]
-            }
+            "name": "startingChips",
+            "type": "u64"
           },
           {
-            "name": "oraclePriceAccount",
-            "type": "pubkey"
+            "name": "marketsMask",
+            "type": "u8"
           },
           {
-            "name": "lockTs",
+            "name": "startTs",
             "type": "i64"
           },
           {
@@ -930,83 +8708,107 @@ export type PanchoPvp = {
             "type": "i64"
           },
           {
-            "name": "startPrice",
-            "type": "i64"
-          },
-          {
-            "name": "endPrice",
-            "type": "i64"
+            "name": "entrantCount",
+            "type": "u32"
           },
           {
-            "name": "expo",
-            "type": "i32"
+            "name": "table",
+            "type": {
+              "defined": {
+                "name": "rankedTable"
+              }
+            }
           },
           {
-            "name": "status",
+            "name": "bump",
             "type": "u8"
           },
           {
-            "name": "winnerSide",
-            "type": "u8"
+            "name": "openEntrants",
+            "type": "u32"
+          }
+        ]
+      }
+    },
+    {
+      "name": "tournamentClosed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tournament",
+            "type": "pubkey"
           },
           {
-            "name": "upTotal",
+            "name": "sweptLamports",
             "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "tournamentCreated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tournament",
+            "type": "pubkey"
           },
           {
-            "name": "downTotal",
-            "type": "u64"
+            "name": "tournamentId",
+            "type": "u32"
           },
           {
-            "name": "feeLamports",
+            "name": "entryFeeLamports",
             "type": "u64"
           },
           {
-            "name": "distributableLamports",
+            "name": "startingChips",
             "type": "u64"
           },
           {
-            "name": "bump",
+            "name": "marketsMask",
             "type": "u8"
+          },
+          {
+            "name": "startTs",
+            "type": "i64"
+          },
+          {
+            "name": "endTs",
+            "type": "i64"
           }
         ]
       }
     },
     {
-      "name": "roundCreated",
+      "name": "tournamentEntered",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "round",
+            "name": "tournament",
             "type": "pubkey"
           },
           {
-            "name": "roundId",
-            "type": "i64"
-          },
-          {
-            "name": "market",
-            "type": "u8"
-          },
-          {
-            "name": "lockTs",
-            "type": "i64"
+            "name": "user",
+            "type": "pubkey"
           },
           {
-            "name": "endTs",
-            "type": "i64"
+            "name": "chips",
+            "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "roundJoined",
+      "name": "tournamentPrizeClaimed",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "round",
+            "name": "tournament",
             "type": "pubkey"
           },
           {
@@ -1014,7 +8816,7 @@ export type PanchoPvp = {
             "type": "pubkey"
           },
           {
-            "name": "side",
+            "name": "rank",
             "type": "u8"
           },
           {
@@ -1025,61 +8827,110 @@ export type PanchoPvp = {
       }
     },
     {
-      "name": "roundLocked",
+      "name": "userStats",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "round",
+            "name": "user",
             "type": "pubkey"
           },
           {
-            "name": "startPrice",
+            "name": "lastVolumeDay",
             "type": "i64"
           },
           {
-            "name": "expo",
-            "type": "i32"
+            "name": "dailyVolumeLamports",
+            "type": {
+              "array": [
+                "u64",
+                30
+              ]
+            }
           },
           {
-            "name": "lockedAt",
-            "type": "i64"
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "totalStakedLamports",
+            "type": "u64"
+          },
+          {
+            "name": "totalPayoutLamports",
+            "type": "u64"
+          },
+          {
+            "name": "roundsPlayed",
+            "type": "u32"
+          },
+          {
+            "name": "wins",
+            "type": "u32"
+          },
+          {
+            "name": "losses",
+            "type": "u32"
+          },
+          {
+            "name": "voids",
+            "type": "u32"
+          },
+          {
+            "name": "currentStreak",
+            "type": "u32"
+          },
+          {
+            "name": "bestStreak",
+            "type": "u32"
           }
         ]
       }
     },
     {
-      "name": "roundSettled",
+      "name": "userStatsSnapshot",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "round",
+            "name": "user",
             "type": "pubkey"
           },
           {
-            "name": "winnerSide",
-            "type": "u8"
+            "name": "volume30dLamports",
+            "type": "u64"
           },
           {
-            "name": "startPrice",
-            "type": "i64"
+            "name": "totalStakedLamports",
+            "type": "u64"
           },
           {
-            "name": "endPrice",
-            "type": "i64"
+            "name": "totalPayoutLamports",
+            "type": "u64"
           },
           {
-            "name": "feeLamports",
-            "type": "u64"
+            "name": "roundsPlayed",
+            "type": "u32"
           },
           {
-            "name": "distributableLamports",
-            "type": "u64"
+            "name": "wins",
+            "type": "u32"
           },
           {
-            "name": "settledAt",
-            "type": "i64"
+            "name": "losses",
+            "type": "u32"
+          },
+          {
+            "name": "voids",
+            "type": "u32"
+          },
+          {
+            "name": "currentStreak",
+            "type": "u32"
+          },
+          {
+            "name": "bestStreak",
+            "type": "u32"
           }
         ]
       }
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
 
 declare_id!("52nguesHaBuF4psFr2uybVnW4angLW2ZtsBRSRmdF8k3");
 
@@ -11,6 +12,31 @@ const ROUND_OPEN: u8 = 0;
 const ROUND_LOCKED: u8 = 1;
 const ROUND_SETTLED: u8 = 2;
 const LOCK_GRACE_SECONDS: i64 = 180;
+const DEFAULT_CLAIM_WINDOW_SECONDS: i64 = 30 * 24 * 60 * 60;
+const JOIN_LEG_ACCOUNTS: usize = 3;
+const CLAIM_TUPLE_ACCOUNTS: usize = 4;
+const MAX_FEE_RECIPIENTS: usize = 5;
+const INSURANCE_TARGET_VAULT: u8 = 0;
+const INSURANCE_TARGET_POSITION: u8 = 1;
+const MARKET_COUNT: usize = 3;
+const MAX_FEE_TIERS: usize = 5;
+const VOLUME_WINDOW_DAYS: usize = 30;
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+const LEADERBOARD_SIZE: usize = 10;
+const LEADERBOARD_GRACE_SECONDS: i64 = 24 * 60 * 60;
+const MAX_HOST_FEE_BPS: u16 = 1_000;
+const DUEL_OPEN: u8 = 0;
+const DUEL_LOCKED: u8 = 1;
+const DUEL_ACCEPTED: u8 = 2;
+const DUEL_SETTLE_GRACE_SECONDS: i64 = 180;
+const ORACLE_PUBLISH_TOLERANCE_SECONDS: i64 = 10;
+const REVEAL_WINDOW_SECONDS: i64 = 60;
+const DEFAULT_ORACLE_MAX_AGE_SECONDS: u32 = 60;
+const MAX_DUEL_DURATION_SECONDS: i64 = 24 * 60 * 60;
+const MAX_OFFER_ODDS_BPS: u32 = 1_000_000;
+const ORACLE_KIND_LEGACY: u8 = 0;
+const ORACLE_KIND_PRICE_UPDATE_V2: u8 = 1;
+const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
 
 #[program]
 pub mod pancho_pvp {
@@ -38,6 +64,34 @@ pub mod pancho_pvp {
         config.oracle_account_eth = oracle_account_eth;
         config.paused = false;
         config.bump = ctx.bumps.config;
+        config.crank_tip_bps = 0;
+        config.crank_tip_lamports = 0;
+        config.claim_window_seconds = DEFAULT_CLAIM_WINDOW_SECONDS;
+        config.exit_fee_bps = 0;
+        config.switch_fee_bps = 0;
+        config.max_position_lamports = 0;
+        config.max_round_lamports = 0;
+        config.min_side_participants = 0;
+        config.min_pool_lamports = 0;
+        config.entry_cutoff_seconds_sol = 0;
+        config.entry_cutoff_seconds_btc = 0;
+        config.entry_cutoff_seconds_eth = 0;
+        config.time_weighted_rounds = false;
+        config.time_weight_floor_bps = BPS_DENOMINATOR as u16;
+        config.referral_fee_bps = 0;
+        config.fee_split_count = 0;
+        config.fee_split_recipients = [Pubkey::default(); MAX_FEE_RECIPIENTS];
+        config.fee_split_bps = [0; MAX_FEE_RECIPIENTS];
+        config.insurance_fee_bps = 0;
+        config.sweep_to_insurance = false;
+        config.fee_tier_count = 0;
+        config.fee_tier_volume_lamports = [0; MAX_FEE_TIERS];
+        config.fee_tier_bps = [0; MAX_FEE_TIERS];
+        config.active_season = Pubkey::default();
+        config.commit_reveal_rounds = false;
+        config.forfeit_unrevealed = true;
+        config.oracle_kinds = [ORACLE_KIND_LEGACY; MARKET_COUNT];
+        config.oracle_max_age_seconds = DEFAULT_ORACLE_MAX_AGE_SECONDS;
 
         Ok(())
     }
@@ -77,648 +131,5320 @@ pub mod pancho_pvp {
         Ok(())
     }
 
-    pub fn create_round(
-        ctx: Context<CreateRound>,
-        market: u8,
-        round_id: i64,
-        lock_ts: i64,
-        end_ts: i64,
-        feed_id: [u8; 32],
-        oracle_price_account: Pubkey,
+    /// Selects the legacy push account or the receiver's PriceUpdateV2 for one market.
+    /// Rounds keep the kind they were created with.
+    pub fn set_oracle_kind(ctx: Context<SetOracleKind>, market: u8, oracle_kind: u8) -> Result<()> {
+        require!(
+            oracle_kind == ORACLE_KIND_LEGACY || oracle_kind == ORACLE_KIND_PRICE_UPDATE_V2,
+            PanchoError::InvalidOracleKind
+        );
+        let config = &mut ctx.accounts.config;
+        config.oracle_kinds[market_index(market)?] = oracle_kind;
+        Ok(())
+    }
+
+    /// PriceUpdateV2 max age in seconds; `oracle_max_age_sec` stays the legacy slot age.
+    pub fn set_oracle_max_age_seconds(
+        ctx: Context<SetOracleMaxAgeSeconds>,
+        oracle_max_age_seconds: u32,
     ) -> Result<()> {
-        require!(!ctx.accounts.config.paused, PanchoError::ProtocolPaused);
-        require!(end_ts > lock_ts, PanchoError::InvalidSchedule);
+        require!(oracle_max_age_seconds > 0, PanchoError::InvalidOracleMaxAge);
+        ctx.accounts.config.oracle_max_age_seconds = oracle_max_age_seconds;
+        Ok(())
+    }
 
-        let now = Clock::get()?.unix_timestamp;
-        require!(lock_ts > now, PanchoError::InvalidSchedule);
-        require!(market <= 2, PanchoError::InvalidMarket);
+    /// Grows a config written by an older build to the current layout. Appended fields
+    /// read as zero, apart from the claim window and the PriceUpdateV2 max age, which get
+    /// the `initialize_config` defaults. Safe to run again.
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        let config_info = ctx.accounts.config.to_account_info();
+        {
+            let data = config_info.try_borrow_data()?;
+            require!(
+                data.len() >= 40 && data[..8] == *GlobalConfig::DISCRIMINATOR,
+                PanchoError::InvalidConfigAccount
+            );
+            let admin = Pubkey::try_from(&data[8..40]).map_err(|_| error!(PanchoError::InvalidConfigAccount))?;
+            require_keys_eq!(admin, ctx.accounts.admin.key(), PanchoError::InvalidConfigAccount);
+        }
 
-        let expected_oracle = expected_oracle_account(&ctx.accounts.config, market)?;
-        require_keys_eq!(
-            oracle_price_account,
-            expected_oracle,
-            PanchoError::UnexpectedOracleAccount
+        let new_len = 8 + GlobalConfig::INIT_SPACE;
+        let top_up = Rent::get()?
+            .minimum_balance(new_len)
+            .saturating_sub(config_info.lamports());
+        if top_up > 0 {
+            let cpi_accounts = anchor_lang::system_program::Transfer {
+                from: ctx.accounts.admin.to_account_info(),
+                to: config_info.clone(),
+            };
+            let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+            anchor_lang::system_program::transfer(cpi_ctx, top_up)?;
+        }
+        if config_info.data_len() < new_len {
+            config_info.resize(new_len)?;
+        }
+
+        let mut data = config_info.try_borrow_mut_data()?;
+        let mut config = GlobalConfig::try_deserialize(&mut &data[..])?;
+        if config.claim_window_seconds == 0 {
+            config.claim_window_seconds = DEFAULT_CLAIM_WINDOW_SECONDS;
+        }
+        if config.oracle_max_age_seconds == 0 {
+            config.oracle_max_age_seconds = DEFAULT_ORACLE_MAX_AGE_SECONDS;
+        }
+        config.try_serialize(&mut &mut data[..])
+    }
+
+    pub fn set_crank_tip(
+        ctx: Context<SetCrankTip>,
+        crank_tip_bps: u16,
+        crank_tip_lamports: u64,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.crank_tip_bps = crank_tip_bps;
+        config.crank_tip_lamports = crank_tip_lamports;
+        require!(
+            reserved_fee_bps(config) <= BPS_DENOMINATOR,
+            PanchoError::InvalidFeeBps
         );
-        let expected_feed = expected_feed_id(market)?;
-        require!(feed_id == expected_feed, PanchoError::InvalidFeedId);
+        Ok(())
+    }
 
-        let round = &mut ctx.accounts.round;
-        round.round_id = round_id;
-        round.market = market;
-        round.feed_id = feed_id;
-        round.oracle_price_account = oracle_price_account;
-        round.lock_ts = lock_ts;
-        round.end_ts = end_ts;
-        round.start_price = 0;
-        round.end_price = 0;
-        round.expo = 0;
-        round.status = ROUND_OPEN;
-        round.winner_side = SIDE_NONE;
-        round.up_total = 0;
-        round.down_total = 0;
-        round.fee_lamports = 0;
-        round.distributable_lamports = 0;
-        round.bump = ctx.bumps.round;
+    pub fn set_claim_window(ctx: Context<SetClaimWindow>, claim_window_seconds: i64) -> Result<()> {
+        require!(claim_window_seconds >= 0, PanchoError::InvalidSchedule);
 
-        let up_vault = &mut ctx.accounts.up_vault;
-        up_vault.round = round.key();
-        up_vault.side = SIDE_UP;
-        up_vault.bump = ctx.bumps.up_vault;
+        let config = &mut ctx.accounts.config;
+        config.claim_window_seconds = claim_window_seconds;
+        Ok(())
+    }
 
-        let down_vault = &mut ctx.accounts.down_vault;
-        down_vault.round = round.key();
-        down_vault.side = SIDE_DOWN;
-        down_vault.bump = ctx.bumps.down_vault;
+    pub fn set_exit_fee(ctx: Context<SetExitFee>, exit_fee_bps: u16) -> Result<()> {
+        require!(exit_fee_bps <= 1_500, PanchoError::InvalidFeeBps);
 
-        emit!(RoundCreated {
-            round: round.key(),
-            round_id,
-            market,
-            lock_ts,
-            end_ts
-        });
+        let config = &mut ctx.accounts.config;
+        config.exit_fee_bps = exit_fee_bps;
+        Ok(())
+    }
+
+    pub fn set_switch_fee(ctx: Context<SetSwitchFee>, switch_fee_bps: u16) -> Result<()> {
+        require!(switch_fee_bps <= 1_500, PanchoError::InvalidFeeBps);
 
+        let config = &mut ctx.accounts.config;
+        config.switch_fee_bps = switch_fee_bps;
         Ok(())
     }
 
-    pub fn join_round(ctx: Context<JoinRound>, side: u8, lamports: u64) -> Result<()> {
-        require!(!ctx.accounts.config.paused, PanchoError::ProtocolPaused);
-        require!(lamports > 0, PanchoError::InvalidStake);
-        require!(side == SIDE_UP || side == SIDE_DOWN, PanchoError::InvalidSide);
+    pub fn set_stake_limits(
+        ctx: Context<SetStakeLimits>,
+        max_position_lamports: u64,
+        max_round_lamports: u64,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.max_position_lamports = max_position_lamports;
+        config.max_round_lamports = max_round_lamports;
+        Ok(())
+    }
 
-        let now = Clock::get()?.unix_timestamp;
-        let round = &mut ctx.accounts.round;
+    pub fn set_participation_rules(
+        ctx: Context<SetParticipationRules>,
+        min_side_participants: u32,
+        min_pool_lamports: u64,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.min_side_participants = min_side_participants;
+        config.min_pool_lamports = min_pool_lamports;
+        Ok(())
+    }
 
-        require!(round.status == ROUND_OPEN, PanchoError::RoundNotOpen);
-        require!(now < round.lock_ts, PanchoError::RoundLocked);
+    pub fn set_entry_cutoffs(
+        ctx: Context<SetEntryCutoffs>,
+        entry_cutoff_seconds_sol: u32,
+        entry_cutoff_seconds_btc: u32,
+        entry_cutoff_seconds_eth: u32,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.entry_cutoff_seconds_sol = entry_cutoff_seconds_sol;
+        config.entry_cutoff_seconds_btc = entry_cutoff_seconds_btc;
+        config.entry_cutoff_seconds_eth = entry_cutoff_seconds_eth;
+        Ok(())
+    }
 
-        let cpi_accounts = anchor_lang::system_program::Transfer {
-            from: ctx.accounts.user.to_account_info(),
-            to: ctx.accounts.side_vault.to_account_info(),
-        };
-        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
-        anchor_lang::system_program::transfer(cpi_ctx, lamports)?;
+    pub fn set_time_weighting(
+        ctx: Context<SetTimeWeighting>,
+        time_weighted_rounds: bool,
+        time_weight_floor_bps: u16,
+    ) -> Result<()> {
+        require!(
+            time_weight_floor_bps as u64 <= BPS_DENOMINATOR,
+            PanchoError::InvalidFeeBps
+        );
 
-        let position = &mut ctx.accounts.position;
-        if position.amount == 0 {
-            position.round = round.key();
-            position.user = ctx.accounts.user.key();
-            position.side = side;
-            position.claimed = false;
-            position.bump = ctx.bumps.position;
-        }
-        require!(position.side == side, PanchoError::PositionSideMismatch);
-        require!(!position.claimed, PanchoError::AlreadyClaimed);
-        position.amount = position
-            .amount
-            .checked_add(lamports)
-            .ok_or(PanchoError::MathOverflow)?;
+        let config = &mut ctx.accounts.config;
+        config.time_weighted_rounds = time_weighted_rounds;
+        config.time_weight_floor_bps = time_weight_floor_bps;
+        Ok(())
+    }
 
-        if side == SIDE_UP {
-            round.up_total = round
-                .up_total
-                .checked_add(lamports)
-                .ok_or(PanchoError::MathOverflow)?;
-        } else {
-            round.down_total = round
-                .down_total
-                .checked_add(lamports)
-                .ok_or(PanchoError::MathOverflow)?;
+    pub fn set_commit_reveal(
+        ctx: Context<SetCommitReveal>,
+        commit_reveal_rounds: bool,
+        forfeit_unrevealed: bool,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.commit_reveal_rounds = commit_reveal_rounds;
+        config.forfeit_unrevealed = forfeit_unrevealed;
+        Ok(())
+    }
+
+    pub fn set_referral_fee(ctx: Context<SetReferralFee>, referral_fee_bps: u16) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.referral_fee_bps = referral_fee_bps;
+        require!(
+            reserved_fee_bps(config) <= BPS_DENOMINATOR,
+            PanchoError::InvalidFeeBps
+        );
+        Ok(())
+    }
+
+    pub fn set_insurance_config(
+        ctx: Context<SetInsuranceConfig>,
+        insurance_fee_bps: u16,
+        sweep_to_insurance: bool,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.insurance_fee_bps = insurance_fee_bps;
+        config.sweep_to_insurance = sweep_to_insurance;
+        require!(
+            reserved_fee_bps(config) <= BPS_DENOMINATOR,
+            PanchoError::InvalidFeeBps
+        );
+        Ok(())
+    }
+
+    pub fn initialize_insurance_fund(ctx: Context<InitializeInsuranceFund>) -> Result<()> {
+        let insurance_fund = &mut ctx.accounts.insurance_fund;
+        insurance_fund.total_deposited_lamports = 0;
+        insurance_fund.total_paid_out_lamports = 0;
+        insurance_fund.bump = ctx.bumps.insurance_fund;
+        Ok(())
+    }
+
+    pub fn insurance_payout(ctx: Context<InsurancePayout>, target: u8, lamports: u64) -> Result<()> {
+        require!(lamports > 0, PanchoError::InvalidStake);
+
+        let round = &mut ctx.accounts.round;
+        require!(round.status == ROUND_SETTLED, PanchoError::RoundNotSettled);
+        let round_key = round.key();
+        let recipient = ctx.accounts.recipient.to_account_info();
+        match target {
+            INSURANCE_TARGET_VAULT => {
+                require_keys_eq!(*recipient.owner, *ctx.program_id, PanchoError::VaultRoundMismatch);
+                let vault = Vault::try_deserialize(&mut &recipient.try_borrow_data()?[..])?;
+                require_keys_eq!(vault.round, round_key, PanchoError::VaultRoundMismatch);
+                let expected_vault = Pubkey::create_program_address(
+                    &[b"vault", round_key.as_ref(), &[vault.side], &[vault.bump]],
+                    ctx.program_id,
+                )
+                .map_err(|_| error!(PanchoError::VaultRoundMismatch))?;
+                require_keys_eq!(recipient.key(), expected_vault, PanchoError::VaultRoundMismatch);
+                // Topping up a vault only helps claimants if the pool they split grows too,
+                // and the pool can only grow while every claimant still gets the same share.
+                require!(round.claimed_positions == 0, PanchoError::InsuranceAfterClaims);
+                round.distributable_lamports = round
+                    .distributable_lamports
+                    .checked_add(lamports)
+                    .ok_or(PanchoError::MathOverflow)?;
+            }
+            INSURANCE_TARGET_POSITION => {
+                // A direct payout covers what the position's own claim falls short of its
+                // stake; the claim itself stays open, so winners keep their winnings.
+                let position = ctx
+                    .accounts
+                    .position
+                    .as_mut()
+                    .ok_or(PanchoError::InvalidPositionAccount)?;
+                require_keys_eq!(position.round, round_key, PanchoError::PositionRoundMismatch);
+                require_keys_eq!(recipient.key(), position.user, PanchoError::PositionUserMismatch);
+                require!(position.amount > 0, PanchoError::NothingToClaim);
+                let insured = position
+                    .insured_lamports
+                    .checked_add(lamports)
+                    .ok_or(PanchoError::MathOverflow)?;
+                require!(
+                    insured <= insurable_shortfall(round, position)?,
+                    PanchoError::InsurancePayoutExceedsStake
+                );
+                position.insured_lamports = insured;
+            }
+            _ => return err!(PanchoError::InvalidInsuranceTarget),
         }
 
-        emit!(RoundJoined {
-            round: round.key(),
-            user: ctx.accounts.user.key(),
-            side,
+        let insurance_fund = &mut ctx.accounts.insurance_fund;
+        insurance_fund.total_paid_out_lamports = insurance_fund
+            .total_paid_out_lamports
+            .checked_add(lamports)
+            .ok_or(PanchoError::MathOverflow)?;
+        transfer_from_vault(&insurance_fund.to_account_info(), &recipient, lamports)?;
+
+        emit!(InsurancePaidOut {
+            round: round_key,
+            recipient: recipient.key(),
+            target,
             lamports,
         });
 
         Ok(())
     }
 
-    pub fn lock_round(ctx: Context<LockRound>) -> Result<()> {
-        require!(!ctx.accounts.config.paused, PanchoError::ProtocolPaused);
+    pub fn initialize_house_vault(ctx: Context<InitializeHouseVault>) -> Result<()> {
+        let house_vault = &mut ctx.accounts.house_vault;
+        house_vault.enabled = false;
+        house_vault.seed_cap_lamports = 0;
+        house_vault.total_funded_lamports = 0;
+        house_vault.total_withdrawn_lamports = 0;
+        house_vault.seeded_lamports = [0; MARKET_COUNT];
+        house_vault.returned_lamports = [0; MARKET_COUNT];
+        house_vault.pnl_lamports = [0; MARKET_COUNT];
+        house_vault.bump = ctx.bumps.house_vault;
+        Ok(())
+    }
 
-        let now = Clock::get()?.unix_timestamp;
-        let round = &mut ctx.accounts.round;
+    pub fn set_house_config(
+        ctx: Context<SetHouseConfig>,
+        seed_cap_lamports: u64,
+        enabled: bool,
+    ) -> Result<()> {
+        let house_vault = &mut ctx.accounts.house_vault;
+        house_vault.seed_cap_lamports = seed_cap_lamports;
+        house_vault.enabled = enabled;
+        Ok(())
+    }
 
-        require!(round.status == ROUND_OPEN, PanchoError::RoundAlreadyLocked);
-        require!(now >= round.lock_ts, PanchoError::TooEarlyToLock);
-        require!(now <= round.lock_ts + LOCK_GRACE_SECONDS, PanchoError::LockWindowExpired);
+    pub fn fund_house_vault(ctx: Context<FundHouseVault>, lamports: u64) -> Result<()> {
+        require!(lamports > 0, PanchoError::InvalidStake);
 
-        let clock = Clock::get()?;
-        let price = read_legacy_pyth_price(
-            &ctx.accounts.oracle_price,
-            round.oracle_price_account,
-            clock.slot,
-            ctx.accounts.config.oracle_max_age_sec as u64,
-            ctx.accounts.config.oracle_program,
-        )?;
+        let cpi_accounts = anchor_lang::system_program::Transfer {
+            from: ctx.accounts.treasury.to_account_info(),
+            to: ctx.accounts.house_vault.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        anchor_lang::system_program::transfer(cpi_ctx, lamports)?;
 
-        round.start_price = price.price;
-        round.expo = price.expo;
-        round.status = ROUND_LOCKED;
+        let house_vault = &mut ctx.accounts.house_vault;
+        house_vault.total_funded_lamports = house_vault
+            .total_funded_lamports
+            .checked_add(lamports)
+            .ok_or(PanchoError::MathOverflow)?;
 
-        emit!(RoundLocked {
-            round: round.key(),
-            start_price: round.start_price,
-            expo: round.expo,
-            locked_at: now,
-        });
+        emit!(HouseVaultFunded { lamports });
 
         Ok(())
     }
 
-    pub fn settle_round(ctx: Context<SettleRound>) -> Result<()> {
-        require!(!ctx.accounts.config.paused, PanchoError::ProtocolPaused);
+    pub fn withdraw_house_vault(ctx: Context<WithdrawHouseVault>, lamports: u64) -> Result<()> {
+        require!(lamports > 0, PanchoError::InvalidStake);
 
-        let now = Clock::get()?.unix_timestamp;
-        let round = &mut ctx.accounts.round;
+        let house_info = ctx.accounts.house_vault.to_account_info();
+        let spare = house_info
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(house_info.data_len()));
+        require!(lamports <= spare, PanchoError::InsufficientVaultLiquidity);
+        transfer_from_vault(&house_info, &ctx.accounts.treasury.to_account_info(), lamports)?;
 
-        require!(now >= round.end_ts, PanchoError::TooEarlyToSettle);
-        require!(round.status != ROUND_SETTLED, PanchoError::RoundAlreadySettled);
+        let house_vault = &mut ctx.accounts.house_vault;
+        house_vault.total_withdrawn_lamports = house_vault
+            .total_withdrawn_lamports
+            .checked_add(lamports)
+            .ok_or(PanchoError::MathOverflow)?;
 
-        if round.status == ROUND_OPEN {
-            round.status = ROUND_SETTLED;
-            round.winner_side = SIDE_NONE;
+        emit!(HouseVaultWithdrawn { lamports });
+
+        Ok(())
+    }
+
+    pub fn set_fee_split(
+        ctx: Context<SetFeeSplit>,
+        recipients: Vec<Pubkey>,
+        recipient_bps: Vec<u16>,
+    ) -> Result<()> {
+        require!(
+            recipients.len() <= MAX_FEE_RECIPIENTS && recipients.len() == recipient_bps.len(),
+            PanchoError::InvalidFeeSplit
+        );
+        let total_bps: u64 = recipient_bps.iter().map(|bps| *bps as u64).sum();
+        require!(
+            recipients.is_empty() || total_bps == BPS_DENOMINATOR,
+            PanchoError::InvalidFeeSplit
+        );
+        // Recipients are passed as remaining accounts and must already be rent-exempt,
+        // so small shares can be credited to them.
+        require!(
+            ctx.remaining_accounts.len() == recipients.len(),
+            PanchoError::FeeRecipientMismatch
+        );
+        let rent = Rent::get()?;
+        for (recipient, expected) in ctx.remaining_accounts.iter().zip(&recipients) {
+            require_keys_eq!(recipient.key(), *expected, PanchoError::FeeRecipientMismatch);
+            require!(
+                rent.is_exempt(recipient.lamports(), recipient.data_len()),
+                PanchoError::FeeRecipientNotRentExempt
+            );
         }
 
-        if round.status == ROUND_LOCKED {
-            let clock = Clock::get()?;
-            let price = read_legacy_pyth_price(
-                &ctx.accounts.oracle_price,
-                round.oracle_price_account,
-                clock.slot,
-                ctx.accounts.config.oracle_max_age_sec as u64,
-                ctx.accounts.config.oracle_program,
-            )?;
-            round.end_price = price.price;
+        let config = &mut ctx.accounts.config;
+        config.fee_split_count = recipients.len() as u8;
+        config.fee_split_recipients = [Pubkey::default(); MAX_FEE_RECIPIENTS];
+        config.fee_split_bps = [0; MAX_FEE_RECIPIENTS];
+        config.fee_split_recipients[..recipients.len()].copy_from_slice(&recipients);
+        config.fee_split_bps[..recipient_bps.len()].copy_from_slice(&recipient_bps);
+        Ok(())
+    }
 
-            if round.up_total == 0 || round.down_total == 0 || round.start_price == round.end_price {
-                round.winner_side = SIDE_NONE;
-            } else if round.end_price > round.start_price {
-                round.winner_side = SIDE_UP;
-            } else {
-                round.winner_side = SIDE_DOWN;
-            }
+    pub fn set_fee_tiers(
+        ctx: Context<SetFeeTiers>,
+        volume_thresholds: Vec<u64>,
+        tier_bps: Vec<u16>,
+    ) -> Result<()> {
+        require!(
+            volume_thresholds.len() <= MAX_FEE_TIERS && volume_thresholds.len() == tier_bps.len(),
+            PanchoError::InvalidFeeTiers
+        );
+        require!(
+            volume_thresholds.windows(2).all(|pair| pair[0] < pair[1]),
+            PanchoError::InvalidFeeTiers
+        );
+        require!(tier_bps.iter().all(|bps| *bps <= 1_500), PanchoError::InvalidFeeBps);
 
-            round.status = ROUND_SETTLED;
+        let config = &mut ctx.accounts.config;
+        config.fee_tier_count = volume_thresholds.len() as u8;
+        config.fee_tier_volume_lamports = [0; MAX_FEE_TIERS];
+        config.fee_tier_bps = [0; MAX_FEE_TIERS];
+        config.fee_tier_volume_lamports[..volume_thresholds.len()].copy_from_slice(&volume_thresholds);
+        config.fee_tier_bps[..tier_bps.len()].copy_from_slice(&tier_bps);
+        Ok(())
+    }
+
+    pub fn create_season(
+        ctx: Context<CreateSeason>,
+        season_id: u32,
+        start_ts: i64,
+        end_ts: i64,
+        prize_bps: Vec<u16>,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(start_ts >= now && end_ts > start_ts, PanchoError::InvalidSeason);
+        require!(prize_bps.len() <= LEADERBOARD_SIZE, PanchoError::InvalidSeason);
+        let total_bps: u64 = prize_bps.iter().map(|bps| *bps as u64).sum();
+        require!(total_bps <= BPS_DENOMINATOR, PanchoError::InvalidSeason);
+        // A new season only replaces one whose prize table is already final.
+        if ctx.accounts.config.active_season != Pubkey::default() {
+            let current = ctx
+                .accounts
+                .current_season
+                .as_ref()
+                .ok_or(PanchoError::SeasonAccountsRequired)?;
+            require_keys_eq!(current.key(), ctx.accounts.config.active_season, PanchoError::SeasonMismatch);
+            require!(now >= ranking_final_ts(current.end_ts)?, PanchoError::SeasonNotFinalized);
         }
 
-        let total = round
-            .up_total
-            .checked_add(round.down_total)
-            .ok_or(PanchoError::MathOverflow)?;
-        let should_charge_fee = round.winner_side != SIDE_NONE
-            && round.start_price != 0
-            && round.end_price != 0;
-        round.fee_lamports = if should_charge_fee {
-            total
-                .checked_mul(ctx.accounts.config.fee_bps as u64)
-                .ok_or(PanchoError::MathOverflow)?
-                .checked_div(BPS_DENOMINATOR)
-                .ok_or(PanchoError::MathOverflow)?
-        } else {
-            0
-        };
-        round.distributable_lamports = total
-            .checked_sub(round.fee_lamports)
-            .ok_or(PanchoError::MathOverflow)?;
+        let season = &mut ctx.accounts.season;
+        season.season_id = season_id;
+        season.start_ts = start_ts;
+        season.end_ts = end_ts;
+        season.table = RankedTable::new(&prize_bps);
+        season.bump = ctx.bumps.season;
 
-        transfer_from_vaults(
-            &ctx.accounts.up_vault.to_account_info(),
-            &ctx.accounts.down_vault.to_account_info(),
-            &ctx.accounts.treasury.to_account_info(),
-            round.fee_lamports,
-        )?;
+        ctx.accounts.config.active_season = season.key();
 
-        emit!(RoundSettled {
-            round: round.key(),
-            winner_side: round.winner_side,
-            start_price: round.start_price,
-            end_price: round.end_price,
-            fee_lamports: round.fee_lamports,
-            distributable_lamports: round.distributable_lamports,
-            settled_at: now,
+        emit!(SeasonCreated {
+            season: season.key(),
+            season_id,
+            start_ts,
+            end_ts,
         });
 
         Ok(())
     }
 
-    pub fn claim(ctx: Context<Claim>) -> Result<()> {
-        let round = &ctx.accounts.round;
-        let position = &mut ctx.accounts.position;
+    /// Points joins and claims at an existing season, or clears it when none is passed.
+    pub fn set_active_season(ctx: Context<SetActiveSeason>) -> Result<()> {
+        ctx.accounts.config.active_season = ctx
+            .accounts
+            .season
+            .as_ref()
+            .map_or(Pubkey::default(), |season| season.key());
+        Ok(())
+    }
 
-        require!(round.status == ROUND_SETTLED, PanchoError::RoundNotSettled);
-        require!(!position.claimed, PanchoError::AlreadyClaimed);
-        require!(position.amount > 0, PanchoError::NothingToClaim);
-
-        let payout = if round.winner_side == SIDE_NONE {
-            proportion(position.amount, round.distributable_lamports, round.up_total + round.down_total)?
-        } else if position.side == round.winner_side {
-            let winner_total = if round.winner_side == SIDE_UP {
-                round.up_total
-            } else {
-                round.down_total
-            };
-            proportion(position.amount, round.distributable_lamports, winner_total)?
-        } else {
-            0
+    pub fn fund_season(ctx: Context<FundSeason>, lamports: u64) -> Result<()> {
+        require!(lamports > 0, PanchoError::InvalidStake);
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now < ranking_final_ts(ctx.accounts.season.end_ts)?,
+            PanchoError::SeasonFinalized
+        );
+
+        let cpi_accounts = anchor_lang::system_program::Transfer {
+            from: ctx.accounts.admin.to_account_info(),
+            to: ctx.accounts.season.to_account_info(),
         };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        anchor_lang::system_program::transfer(cpi_ctx, lamports)?;
+
+        ctx.accounts.season.table.add_to_pool(lamports)
+    }
 
-        position.claimed = true;
+    pub fn claim_season_prize(ctx: Context<ClaimSeasonPrize>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let season = &mut ctx.accounts.season;
+        require!(now >= ranking_final_ts(season.end_ts)?, PanchoError::SeasonNotFinalized);
 
-        if payout > 0 {
-            transfer_from_vaults(
-                &ctx.accounts.up_vault.to_account_info(),
-                &ctx.accounts.down_vault.to_account_info(),
-                &ctx.accounts.user.to_account_info(),
-                payout,
-            )?;
-        }
+        let user_key = ctx.accounts.user.key();
+        let (rank, lamports) = season.table.take_prize(user_key)?;
+        transfer_from_vault(
+            &season.to_account_info(),
+            &ctx.accounts.user.to_account_info(),
+            lamports,
+        )?;
 
-        emit!(Claimed {
-            round: round.key(),
-            user: ctx.accounts.user.key(),
-            side: position.side,
-            stake: position.amount,
-            payout,
+        emit!(SeasonPrizeClaimed {
+            season: season.key(),
+            user: user_key,
+            rank: rank as u8,
+            lamports,
         });
 
         Ok(())
     }
-}
 
-fn proportion(numerator: u64, total_out: u64, total_in: u64) -> Result<u64> {
-    if total_in == 0 || total_out == 0 || numerator == 0 {
-        return Ok(0);
+    /// Permissionless, so the final table reflects every player's season PnL rather than
+    /// only whoever traded last.
+    pub fn submit_season_score(ctx: Context<SubmitSeasonScore>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let season = &mut ctx.accounts.season;
+        require!(now >= season.end_ts, PanchoError::SeasonNotEnded);
+        require!(now < ranking_final_ts(season.end_ts)?, PanchoError::SeasonFinalized);
+
+        let stats = &ctx.accounts.season_stats;
+        season.table.record(stats.user, stats.pnl_lamports);
+        Ok(())
     }
 
-    numerator
-        .checked_mul(total_out)
-        .ok_or(error!(PanchoError::MathOverflow))?
-        .checked_div(total_in)
-        .ok_or(error!(PanchoError::MathOverflow))
-}
+    /// Sweeps unclaimed and unallocated prizes to the treasury with the account rent.
+    pub fn close_season(ctx: Context<CloseSeason>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let season = &ctx.accounts.season;
+        require!(
+            now >= ranking_claim_deadline(&ctx.accounts.config, season.end_ts)?,
+            PanchoError::ClaimWindowOpen
+        );
 
-fn expected_oracle_account(config: &GlobalConfig, market: u8) -> Result<Pubkey> {
-    match market {
-        0 => Ok(config.oracle_account_sol),
-        1 => Ok(config.oracle_account_btc),
-        2 => Ok(config.oracle_account_eth),
-        _ => Err(error!(PanchoError::InvalidMarket)),
-    }
-}
+        let config = &mut ctx.accounts.config;
+        if config.active_season == season.key() {
+            config.active_season = Pubkey::default();
+        }
 
-fn expected_feed_id(market: u8) -> Result<[u8; 32]> {
-    match market {
-        0 => Ok(hex_literal::hex!(
-            "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d"
-        )),
-        1 => Ok(hex_literal::hex!(
-            "e62df6c8b4a85fe1a67db44dc12de5db330f7ac66b72dc658afedf0f4a415b43"
-        )),
-        2 => Ok(hex_literal::hex!(
-            "ff61491a931112ddf1bd8147cd1b641375f79f5825126d665480874634fd0ace"
-        )),
-        _ => Err(error!(PanchoError::InvalidMarket)),
+        emit!(SeasonClosed {
+            season: season.key(),
+            swept_lamports: season.to_account_info().lamports(),
+        });
+
+        Ok(())
     }
-}
 
-fn read_legacy_pyth_price(
-    oracle_price: &UncheckedAccount,
-    expected_oracle_key: Pubkey,
-    now_slot: u64,
-    max_slot_age: u64,
-    expected_oracle_program: Pubkey,
-) -> Result<OraclePrice> {
-    require_keys_eq!(
-        oracle_price.key(),
-        expected_oracle_key,
-        PanchoError::UnexpectedOracleAccount
-    );
-    require_keys_eq!(
-        *oracle_price.owner,
-        expected_oracle_program,
-        PanchoError::InvalidOracleOwner
-    );
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_tournament(
+        ctx: Context<CreateTournament>,
+        tournament_id: u32,
+        entry_fee_lamports: u64,
+        starting_chips: u64,
+        markets_mask: u8,
+        start_ts: i64,
+        end_ts: i64,
+        prize_bps: Vec<u16>,
+    ) -> Result<()> {
+        require!(end_ts > start_ts && starting_chips > 0, PanchoError::InvalidTournament);
+        require!(
+            markets_mask != 0 && (markets_mask as usize) < (1 << MARKET_COUNT),
+            PanchoError::InvalidTournament
+        );
+        require!(
+            !prize_bps.is_empty() && prize_bps.len() <= LEADERBOARD_SIZE,
+            PanchoError::InvalidTournament
+        );
+        let total_bps: u64 = prize_bps.iter().map(|bps| *bps as u64).sum();
+        require!(total_bps == BPS_DENOMINATOR, PanchoError::InvalidTournament);
 
-    let oracle_info = oracle_price.to_account_info();
-    let data = oracle_info
-        .try_borrow_data()
-        .map_err(|_| error!(PanchoError::InvalidOraclePrice))?;
-    let parsed = parse_legacy_pyth_price_account(&data).ok_or(error!(PanchoError::InvalidOraclePrice))?;
-    require!(parsed.status == LEGACY_PYTH_STATUS_TRADING, PanchoError::InvalidOraclePrice);
-    require!(
-        now_slot.saturating_sub(parsed.pub_slot) <= max_slot_age,
-        PanchoError::StaleOraclePrice
-    );
+        let tournament = &mut ctx.accounts.tournament;
+        tournament.tournament_id = tournament_id;
+        tournament.entry_fee_lamports = entry_fee_lamports;
+        tournament.starting_chips = starting_chips;
+        tournament.markets_mask = markets_mask;
+        tournament.start_ts = start_ts;
+        tournament.end_ts = end_ts;
+        tournament.entrant_count = 0;
+        tournament.table = RankedTable::new(&prize_bps);
+        tournament.bump = ctx.bumps.tournament;
+        tournament.open_entrants = 0;
 
-    Ok(OraclePrice {
-        price: parsed.price,
-        expo: parsed.expo,
-    })
-}
+        emit!(TournamentCreated {
+            tournament: tournament.key(),
+            tournament_id,
+            entry_fee_lamports,
+            starting_chips,
+            markets_mask,
+            start_ts,
+            end_ts,
+        });
 
-const LEGACY_PYTH_MAGIC: u32 = 0xa1b2c3d4;
-const LEGACY_PYTH_VERSION_2: u32 = 2;
-const LEGACY_PYTH_ACCOUNT_TYPE_PRICE: u32 = 3;
-const LEGACY_PYTH_STATUS_TRADING: u32 = 1;
-const LEGACY_PYTH_OFFSET_MAGIC: usize = 0;
-const LEGACY_PYTH_OFFSET_VERSION: usize = 4;
-const LEGACY_PYTH_OFFSET_ACCOUNT_TYPE: usize = 8;
-const LEGACY_PYTH_OFFSET_EXPO: usize = 20;
-const LEGACY_PYTH_OFFSET_AGG_PRICE: usize = 208;
-const LEGACY_PYTH_OFFSET_AGG_STATUS: usize = 224;
-const LEGACY_PYTH_OFFSET_AGG_PUB_SLOT: usize = 232;
-const LEGACY_PYTH_MIN_LEN: usize = 240;
+        Ok(())
+    }
 
-struct LegacyPythPrice {
-    price: i64,
-    expo: i32,
-    status: u32,
-    pub_slot: u64,
-}
+    pub fn enter_tournament(ctx: Context<EnterTournament>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(now < ctx.accounts.tournament.end_ts, PanchoError::TournamentFinalized);
 
-fn read_u32_le(data: &[u8], offset: usize) -> Option<u32> {
-    let bytes = data.get(offset..offset + 4)?;
-    Some(u32::from_le_bytes(bytes.try_into().ok()?))
-}
+        let entry_fee = ctx.accounts.tournament.entry_fee_lamports;
+        if entry_fee > 0 {
+            let cpi_accounts = anchor_lang::system_program::Transfer {
+                from: ctx.accounts.user.to_account_info(),
+                to: ctx.accounts.tournament.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+            anchor_lang::system_program::transfer(cpi_ctx, entry_fee)?;
+        }
 
-fn read_i32_le(data: &[u8], offset: usize) -> Option<i32> {
-    let bytes = data.get(offset..offset + 4)?;
-    Some(i32::from_le_bytes(bytes.try_into().ok()?))
-}
+        let tournament = &mut ctx.accounts.tournament;
+        let entrant = &mut ctx.accounts.entrant;
+        entrant.tournament = tournament.key();
+        entrant.user = ctx.accounts.user.key();
+        entrant.chips = tournament.starting_chips;
+        entrant.bump = ctx.bumps.entrant;
+        entrant.open_chip_positions = 0;
 
-fn read_i64_le(data: &[u8], offset: usize) -> Option<i64> {
-    let bytes = data.get(offset..offset + 8)?;
-    Some(i64::from_le_bytes(bytes.try_into().ok()?))
-}
+        tournament.entrant_count = tournament
+            .entrant_count
+            .checked_add(1)
+            .ok_or(PanchoError::MathOverflow)?;
+        tournament.open_entrants = tournament
+            .open_entrants
+            .checked_add(1)
+            .ok_or(PanchoError::MathOverflow)?;
+        tournament.table.add_to_pool(entry_fee)?;
+        tournament.table.record(entrant.user, chip_score(entrant.chips)?);
 
-fn read_u64_le(data: &[u8], offset: usize) -> Option<u64> {
-    let bytes = data.get(offset..offset + 8)?;
-    Some(u64::from_le_bytes(bytes.try_into().ok()?))
-}
+        emit!(TournamentEntered {
+            tournament: entrant.tournament,
+            user: entrant.user,
+            chips: entrant.chips,
+        });
 
-fn parse_legacy_pyth_price_account(data: &[u8]) -> Option<LegacyPythPrice> {
-    if data.len() < LEGACY_PYTH_MIN_LEN {
-        return None;
+        Ok(())
     }
 
-    if read_u32_le(data, LEGACY_PYTH_OFFSET_MAGIC)? != LEGACY_PYTH_MAGIC {
-        return None;
+    pub fn join_round_chips(ctx: Context<JoinRoundChips>, side: u8, chips: u64) -> Result<()> {
+        require!(!ctx.accounts.config.paused, PanchoError::ProtocolPaused);
+        require!(chips > 0, PanchoError::InvalidStake);
+        require!(side == SIDE_UP || side == SIDE_DOWN, PanchoError::InvalidSide);
+
+        let now = Clock::get()?.unix_timestamp;
+        let tournament = &mut ctx.accounts.tournament;
+        let round = &mut ctx.accounts.round;
+        require!(
+            tournament.markets_mask & (1 << market_index(round.market)?) != 0
+                && round.open_ts >= tournament.start_ts
+                && round.end_ts <= tournament.end_ts
+                && round.room == Pubkey::default(),
+            PanchoError::TournamentRoundMismatch
+        );
+        require!(round.status == ROUND_OPEN, PanchoError::RoundNotOpen);
+        require!(now < round.lock_ts, PanchoError::RoundLocked);
+        require!(now < round.entry_cutoff_ts, PanchoError::EntryCutoffPassed);
+
+        let entrant = &mut ctx.accounts.entrant;
+        entrant.chips = entrant
+            .chips
+            .checked_sub(chips)
+            .ok_or(PanchoError::InsufficientChips)?;
+
+        let chip_position = &mut ctx.accounts.chip_position;
+        if chip_position.round == Pubkey::default() {
+            add_open_account(round)?;
+            entrant.open_chip_positions = entrant
+                .open_chip_positions
+                .checked_add(1)
+                .ok_or(PanchoError::MathOverflow)?;
+            chip_position.round = round.key();
+            chip_position.entrant = entrant.key();
+            chip_position.side = side;
+            chip_position.bump = ctx.bumps.chip_position;
+        }
+        require!(chip_position.side == side, PanchoError::PositionSideMismatch);
+        chip_position.chips = chip_position
+            .chips
+            .checked_add(chips)
+            .ok_or(PanchoError::MathOverflow)?;
+
+        if side == SIDE_UP {
+            round.up_chips = round.up_chips.checked_add(chips).ok_or(PanchoError::MathOverflow)?;
+        } else {
+            round.down_chips = round.down_chips.checked_add(chips).ok_or(PanchoError::MathOverflow)?;
+        }
+
+        tournament.table.record(entrant.user, chip_score(entrant.chips)?);
+
+        emit!(ChipsJoined {
+            round: round.key(),
+            tournament: tournament.key(),
+            user: entrant.user,
+            side,
+            chips,
+        });
+
+        Ok(())
     }
-    if read_u32_le(data, LEGACY_PYTH_OFFSET_VERSION)? != LEGACY_PYTH_VERSION_2 {
-        return None;
+
+    /// Permissionless, so a settled round's chip positions can always be cleared before
+    /// `close_round`. Chips credited after the table freezes no longer move the ranking.
+    pub fn claim_chips(ctx: Context<ClaimChips>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let round = &mut ctx.accounts.round;
+        require!(round.status == ROUND_SETTLED, PanchoError::RoundNotSettled);
+        remove_open_account(round)?;
+
+        let chip_position = &ctx.accounts.chip_position;
+        let payout = chip_payout(round, chip_position)?;
+        let entrant = &mut ctx.accounts.entrant;
+        entrant.chips = entrant
+            .chips
+            .checked_add(payout)
+            .ok_or(PanchoError::MathOverflow)?;
+        entrant.open_chip_positions = entrant
+            .open_chip_positions
+            .checked_sub(1)
+            .ok_or(PanchoError::MathOverflow)?;
+
+        let tournament = &mut ctx.accounts.tournament;
+        if now < ranking_final_ts(tournament.end_ts)? {
+            tournament.table.record(entrant.user, chip_score(entrant.chips)?);
+        }
+
+        emit!(ChipsClaimed {
+            round: round.key(),
+            tournament: entrant.tournament,
+            user: entrant.user,
+            side: chip_position.side,
+            stake: chip_position.chips,
+            payout,
+        });
+
+        Ok(())
     }
-    if read_u32_le(data, LEGACY_PYTH_OFFSET_ACCOUNT_TYPE)? != LEGACY_PYTH_ACCOUNT_TYPE_PRICE {
-        return None;
+
+    pub fn claim_tournament_prize(ctx: Context<ClaimTournamentPrize>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let tournament = &mut ctx.accounts.tournament;
+        require!(
+            now >= ranking_final_ts(tournament.end_ts)?,
+            PanchoError::TournamentNotFinalized
+        );
+
+        let user_key = ctx.accounts.user.key();
+        let (rank, lamports) = tournament.table.take_prize(user_key)?;
+        transfer_from_vault(
+            &tournament.to_account_info(),
+            &ctx.accounts.user.to_account_info(),
+            lamports,
+        )?;
+
+        emit!(TournamentPrizeClaimed {
+            tournament: tournament.key(),
+            user: user_key,
+            rank: rank as u8,
+            lamports,
+        });
+
+        Ok(())
     }
 
-    Some(LegacyPythPrice {
-        price: read_i64_le(data, LEGACY_PYTH_OFFSET_AGG_PRICE)?,
-        expo: read_i32_le(data, LEGACY_PYTH_OFFSET_EXPO)?,
-        status: read_u32_le(data, LEGACY_PYTH_OFFSET_AGG_STATUS)?,
-        pub_slot: read_u64_le(data, LEGACY_PYTH_OFFSET_AGG_PUB_SLOT)?,
-    })
-}
+    /// Ranks an entrant's chip balance after `end_ts`, including chips they won in
+    /// rounds they never claimed themselves.
+    pub fn submit_score(ctx: Context<SubmitScore>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let tournament = &mut ctx.accounts.tournament;
+        require!(now >= tournament.end_ts, PanchoError::TournamentNotEnded);
+        require!(
+            now < ranking_final_ts(tournament.end_ts)?,
+            PanchoError::TournamentFinalized
+        );
 
-fn transfer_from_vaults(up_vault: &AccountInfo, down_vault: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
-    if amount == 0 {
-        return Ok(());
+        let entrant = &ctx.accounts.entrant;
+        tournament.table.record(entrant.user, chip_score(entrant.chips)?);
+        Ok(())
     }
 
-    let mut remaining = amount;
-    let up_available = up_vault.lamports();
-    let take_up = up_available.min(remaining);
-    if take_up > 0 {
-        **up_vault.try_borrow_mut_lamports()? = up_available
-            .checked_sub(take_up)
-            .ok_or(error!(PanchoError::MathOverflow))?;
-        **to.try_borrow_mut_lamports()? = to
-            .lamports()
-            .checked_add(take_up)
-            .ok_or(error!(PanchoError::MathOverflow))?;
-        remaining = remaining
-            .checked_sub(take_up)
-            .ok_or(error!(PanchoError::MathOverflow))?;
+    /// Returns an entrant's rent to its owner once the table is final and its chip
+    /// positions are claimed. Anyone can call it; `close_tournament` waits for all of them.
+    pub fn close_entrant(ctx: Context<CloseEntrant>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let tournament = &mut ctx.accounts.tournament;
+        require!(
+            now >= ranking_final_ts(tournament.end_ts)?,
+            PanchoError::TournamentNotFinalized
+        );
+        require!(
+            ctx.accounts.entrant.open_chip_positions == 0,
+            PanchoError::EntrantHasOpenPositions
+        );
+        tournament.open_entrants = tournament
+            .open_entrants
+            .checked_sub(1)
+            .ok_or(PanchoError::MathOverflow)?;
+        Ok(())
     }
 
-    if remaining > 0 {
-        let down_available = down_vault.lamports();
-        let take_down = down_available.min(remaining);
-        if take_down > 0 {
-            **down_vault.try_borrow_mut_lamports()? = down_available
-                .checked_sub(take_down)
-                .ok_or(error!(PanchoError::MathOverflow))?;
-            **to.try_borrow_mut_lamports()? = to
-                .lamports()
-                .checked_add(take_down)
-                .ok_or(error!(PanchoError::MathOverflow))?;
-            remaining = remaining
-                .checked_sub(take_down)
-                .ok_or(error!(PanchoError::MathOverflow))?;
-        }
+    /// Sweeps unclaimed and unallocated prizes to the treasury with the account rent.
+    pub fn close_tournament(ctx: Context<CloseTournament>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let tournament = &ctx.accounts.tournament;
+        require!(
+            now >= ranking_claim_deadline(&ctx.accounts.config, tournament.end_ts)?,
+            PanchoError::ClaimWindowOpen
+        );
+        require!(tournament.open_entrants == 0, PanchoError::TournamentHasOpenEntrants);
+
+        emit!(TournamentClosed {
+            tournament: tournament.key(),
+            swept_lamports: tournament.to_account_info().lamports(),
+        });
+
+        Ok(())
     }
 
-    require!(remaining == 0, PanchoError::InsufficientVaultLiquidity);
-    Ok(())
-}
+    pub fn create_round(
+        ctx: Context<CreateRound>,
+        market: u8,
+        round_id: i64,
+        lock_ts: i64,
+        end_ts: i64,
+        feed_id: [u8; 32],
+        oracle_price_account: Pubkey,
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.paused, PanchoError::ProtocolPaused);
+        require!(end_ts > lock_ts, PanchoError::InvalidSchedule);
 
-struct OraclePrice {
-    price: i64,
-    expo: i32,
-}
+        let now = Clock::get()?.unix_timestamp;
+        require!(lock_ts > now, PanchoError::InvalidSchedule);
+        require!(market <= 2, PanchoError::InvalidMarket);
 
-#[derive(Accounts)]
-pub struct InitializeConfig<'info> {
-    #[account(mut, address = INITIAL_ADMIN)]
-    pub admin: Signer<'info>,
-    /// CHECK: destination treasury wallet
-    pub treasury: UncheckedAccount<'info>,
+        init_round(
+            &ctx.accounts.config,
+            &mut ctx.accounts.round,
+            NewRound {
+                market,
+                round_id,
+                lock_ts,
+                end_ts,
+                feed_id,
+                oracle_price_account,
+                rent_payer: ctx.accounts.admin.key(),
+                bump: ctx.bumps.round,
+            },
+            now,
+        )?;
+        let round_key = ctx.accounts.round.key();
+        init_round_vault(&mut ctx.accounts.up_vault, round_key, SIDE_UP, ctx.bumps.up_vault);
+        init_round_vault(&mut ctx.accounts.down_vault, round_key, SIDE_DOWN, ctx.bumps.down_vault);
+
+        Ok(())
+    }
+
+    pub fn create_room(
+        ctx: Context<CreateRoom>,
+        room_id: u32,
+        allowlist_root: [u8; 32],
+        stake_lamports: u64,
+        host_fee_bps: u16,
+    ) -> Result<()> {
+        require!(host_fee_bps <= MAX_HOST_FEE_BPS, PanchoError::InvalidRoom);
+
+        let room = &mut ctx.accounts.room;
+        room.host = ctx.accounts.host.key();
+        room.room_id = room_id;
+        room.allowlist_root = allowlist_root;
+        room.stake_lamports = stake_lamports;
+        room.host_fee_bps = host_fee_bps;
+        room.round_count = 0;
+        room.bump = ctx.bumps.room;
+
+        emit!(RoomCreated {
+            room: room.key(),
+            host: room.host,
+            room_id,
+            stake_lamports,
+            host_fee_bps,
+        });
+
+        Ok(())
+    }
+
+    /// Host-created round on one of the regular markets. Joins need an allowlist proof
+    /// against the room's merkle root, and the host takes `host_fee_bps` at settlement.
+    pub fn create_room_round(
+        ctx: Context<CreateRoomRound>,
+        market: u8,
+        round_id: i64,
+        lock_ts: i64,
+        end_ts: i64,
+        feed_id: [u8; 32],
+        oracle_price_account: Pubkey,
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.paused, PanchoError::ProtocolPaused);
+        require!(end_ts > lock_ts, PanchoError::InvalidSchedule);
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(lock_ts > now, PanchoError::InvalidSchedule);
+        require!(market <= 2, PanchoError::InvalidMarket);
+
+        init_round(
+            &ctx.accounts.config,
+            &mut ctx.accounts.round,
+            NewRound {
+                market,
+                round_id,
+                lock_ts,
+                end_ts,
+                feed_id,
+                oracle_price_account,
+                rent_payer: ctx.accounts.host.key(),
+                bump: ctx.bumps.round,
+            },
+            now,
+        )?;
+        let room = &mut ctx.accounts.room;
+        let round = &mut ctx.accounts.round;
+        round.room = room.key();
+        round.room_host = room.host;
+        round.host_fee_bps = room.host_fee_bps;
+        // Commitments bypass the allowlist check in `join_round`, so rooms take direct joins.
+        round.commit_reveal = false;
+        room.round_count = room
+            .round_count
+            .checked_add(1)
+            .ok_or(PanchoError::MathOverflow)?;
+
+        let round_key = round.key();
+        init_round_vault(&mut ctx.accounts.up_vault, round_key, SIDE_UP, ctx.bumps.up_vault);
+        init_round_vault(&mut ctx.accounts.down_vault, round_key, SIDE_DOWN, ctx.bumps.down_vault);
+
+        Ok(())
+    }
+
+    pub fn join_round(
+        ctx: Context<JoinRound>,
+        side: u8,
+        lamports: u64,
+        min_multiplier_bps: Option<u64>,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.paused, PanchoError::ProtocolPaused);
+        check_room_access(
+            &ctx.accounts.round,
+            ctx.accounts.room.as_ref(),
+            ctx.accounts.user.key(),
+            lamports,
+            &proof,
+        )?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let new_position = ctx.accounts.position.amount == 0;
+        let weight = apply_join(
+            &ctx.accounts.config,
+            &mut ctx.accounts.round,
+            &mut ctx.accounts.position,
+            JoinStake {
+                user: ctx.accounts.user.key(),
+                side,
+                lamports,
+                position_bump: ctx.bumps.position,
+                min_multiplier_bps,
+            },
+            now,
+        )?;
+
+        record_join(
+            &mut ctx.accounts.user_stats,
+            ctx.accounts.user.key(),
+            ctx.bumps.user_stats,
+            lamports,
+            new_position,
+            now,
+        )?;
+        apply_season_pnl(
+            &ctx.accounts.config,
+            ctx.accounts.season.as_mut(),
+            ctx.accounts.season_stats.as_ref(),
+            Some((
+                &ctx.accounts.user.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+            )),
+            &ctx.accounts.round,
+            ctx.accounts.user.key(),
+            SeasonPnl::Debit(lamports),
+            now,
+        )?;
+
+        emit!(RoundJoined {
+            round: ctx.accounts.round.key(),
+            user: ctx.accounts.user.key(),
+            side,
+            lamports,
+            weight,
+        });
+
+        let cpi_accounts = anchor_lang::system_program::Transfer {
+            from: ctx.accounts.user.to_account_info(),
+            to: ctx.accounts.side_vault.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        anchor_lang::system_program::transfer(cpi_ctx, lamports)?;
+
+        Ok(())
+    }
+
+    /// Joins several rounds in one instruction. `remaining_accounts` holds one
+    /// `(round, position, side_vault)` triple per leg, in the same order as `legs`.
+    pub fn join_many<'info>(
+        ctx: Context<'_, '_, 'info, 'info, JoinMany<'info>>,
+        legs: Vec<JoinLeg>,
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.paused, PanchoError::ProtocolPaused);
+        require!(!legs.is_empty(), PanchoError::InvalidBatch);
+        require!(
+            ctx.remaining_accounts.len() == legs.len() * JOIN_LEG_ACCOUNTS,
+            PanchoError::InvalidBatch
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let user = ctx.accounts.user.to_account_info();
+        let user_key = user.key();
+        let system_program = ctx.accounts.system_program.to_account_info();
+
+        for (leg, accounts) in legs
+            .iter()
+            .zip(ctx.remaining_accounts.chunks_exact(JOIN_LEG_ACCOUNTS))
+        {
+            require!(leg.side == SIDE_UP || leg.side == SIDE_DOWN, PanchoError::InvalidSide);
+            let (round_info, position_info, vault_info) = (&accounts[0], &accounts[1], &accounts[2]);
+
+            let mut round: Account<Round> = Account::try_from(round_info)?;
+            let round_key = round.key();
+            require!(round.room == Pubkey::default(), PanchoError::RoomRequired);
+
+            load_round_vault(vault_info, round_key, leg.side, ctx.program_id)?;
+
+            let (expected_position, position_bump) = Pubkey::find_program_address(
+                &[b"position", round_key.as_ref(), user_key.as_ref(), &[leg.side]],
+                ctx.program_id,
+            );
+            require_keys_eq!(
+                position_info.key(),
+                expected_position,
+                PanchoError::InvalidPositionAccount
+            );
+            if position_info.owner == &System::id() {
+                create_pda_account(
+                    &user,
+                    position_info,
+                    &system_program,
+                    8 + Position::INIT_SPACE,
+                    &[
+                        b"position",
+                        round_key.as_ref(),
+                        user_key.as_ref(),
+                        &[leg.side],
+                        &[position_bump],
+                    ],
+                    ctx.program_id,
+                )?;
+                position_info.try_borrow_mut_data()?[..8].copy_from_slice(Position::DISCRIMINATOR);
+            }
+            let mut position: Account<Position> = Account::try_from(position_info)?;
+            let new_position = position.amount == 0;
+
+            let weight = apply_join(
+                &ctx.accounts.config,
+                &mut round,
+                &mut position,
+                JoinStake {
+                    user: user_key,
+                    side: leg.side,
+                    lamports: leg.lamports,
+                    position_bump,
+                    min_multiplier_bps: leg.min_multiplier_bps,
+                },
+                now,
+            )?;
+            record_join(
+                &mut ctx.accounts.user_stats,
+                user_key,
+                ctx.bumps.user_stats,
+                leg.lamports,
+                new_position,
+                now,
+            )?;
+            apply_season_pnl(
+                &ctx.accounts.config,
+                ctx.accounts.season.as_mut(),
+                ctx.accounts.season_stats.as_ref(),
+                Some((&user, &system_program)),
+                &round,
+                user_key,
+                SeasonPnl::Debit(leg.lamports),
+                now,
+            )?;
+
+            emit!(RoundJoined {
+                round: round_key,
+                user: user_key,
+                side: leg.side,
+                lamports: leg.lamports,
+                weight,
+            });
+
+            let cpi_accounts = anchor_lang::system_program::Transfer {
+                from: user.clone(),
+                to: vault_info.clone(),
+            };
+            let cpi_ctx = CpiContext::new(system_program.clone(), cpi_accounts);
+            anchor_lang::system_program::transfer(cpi_ctx, leg.lamports)?;
+
+            round.exit(ctx.program_id)?;
+            position.exit(ctx.program_id)?;
+        }
+
+        Ok(())
+    }
+
+    /// Commit-reveal rounds take `hash(side || salt || user)` with the stake before the
+    /// entry cutoff; the stake waits in the commitment PDA until it is revealed.
+    pub fn commit_stake(ctx: Context<CommitStake>, commitment_hash: [u8; 32], lamports: u64) -> Result<()> {
+        require!(!ctx.accounts.config.paused, PanchoError::ProtocolPaused);
+        require!(lamports > 0, PanchoError::InvalidStake);
+
+        let now = Clock::get()?.unix_timestamp;
+        let config = &ctx.accounts.config;
+        let round = &mut ctx.accounts.round;
+        require!(round.commit_reveal, PanchoError::NotCommitRevealRound);
+        require!(round.status == ROUND_OPEN, PanchoError::RoundNotOpen);
+        require!(now < round.lock_ts, PanchoError::RoundLocked);
+        require!(now < round.entry_cutoff_ts, PanchoError::EntryCutoffPassed);
+        require!(
+            config.max_position_lamports == 0 || lamports <= config.max_position_lamports,
+            PanchoError::PositionCapExceeded
+        );
+        round.committed_lamports = round
+            .committed_lamports
+            .checked_add(lamports)
+            .ok_or(PanchoError::MathOverflow)?;
+        require!(
+            config.max_round_lamports == 0 || round.committed_lamports <= config.max_round_lamports,
+            PanchoError::RoundCapExceeded
+        );
+        add_open_account(round)?;
+
+        let cpi_accounts = anchor_lang::system_program::Transfer {
+            from: ctx.accounts.user.to_account_info(),
+            to: ctx.accounts.commitment.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        anchor_lang::system_program::transfer(cpi_ctx, lamports)?;
+
+        let commitment = &mut ctx.accounts.commitment;
+        commitment.round = round.key();
+        commitment.user = ctx.accounts.user.key();
+        commitment.commitment_hash = commitment_hash;
+        commitment.lamports = lamports;
+        commitment.committed_ts = now;
+        commitment.bump = ctx.bumps.commitment;
+
+        emit!(StakeCommitted {
+            round: round.key(),
+            user: commitment.user,
+            lamports,
+        });
+
+        Ok(())
+    }
+
+    /// Reveals a commitment in the short window after `lock_ts`, booking the stake on its
+    /// side as if it had joined at commit time. The commitment PDA is closed to the user.
+    pub fn reveal_stake(ctx: Context<RevealStake>, side: u8, salt: [u8; 32]) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let round = &ctx.accounts.round;
+        require!(round.status != ROUND_SETTLED, PanchoError::RevealWindowClosed);
+        require!(now >= round.lock_ts, PanchoError::RevealWindowOpen);
+        require!(now < reveal_deadline(round)?, PanchoError::RevealWindowClosed);
+        require!(side == SIDE_UP || side == SIDE_DOWN, PanchoError::InvalidSide);
+
+        let commitment = &ctx.accounts.commitment;
+        let user_key = ctx.accounts.user.key();
+        require!(
+            commitment_hash(side, &salt, user_key) == commitment.commitment_hash,
+            PanchoError::InvalidReveal
+        );
+        let lamports = commitment.lamports;
+        let committed_ts = commitment.committed_ts;
+
+        let new_position = ctx.accounts.position.amount == 0;
+        let weight = add_stake(
+            &ctx.accounts.config,
+            &mut ctx.accounts.round,
+            &mut ctx.accounts.position,
+            JoinStake {
+                user: user_key,
+                side,
+                lamports,
+                position_bump: ctx.bumps.position,
+                min_multiplier_bps: None,
+            },
+            committed_ts,
+        )?;
+        let round = &mut ctx.accounts.round;
+        round.revealed_lamports = round
+            .revealed_lamports
+            .checked_add(lamports)
+            .ok_or(PanchoError::MathOverflow)?;
+        remove_open_account(round)?;
+        transfer_from_vault(
+            &ctx.accounts.commitment.to_account_info(),
+            &ctx.accounts.side_vault.to_account_info(),
+            lamports,
+        )?;
+
+        record_join(
+            &mut ctx.accounts.user_stats,
+            user_key,
+            ctx.bumps.user_stats,
+            lamports,
+            new_position,
+            now,
+        )?;
+        apply_season_pnl(
+            &ctx.accounts.config,
+            ctx.accounts.season.as_mut(),
+            ctx.accounts.season_stats.as_ref(),
+            Some((
+                &ctx.accounts.user.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+            )),
+            &ctx.accounts.round,
+            user_key,
+            SeasonPnl::Debit(lamports),
+            now,
+        )?;
+
+        emit!(StakeRevealed {
+            round: ctx.accounts.round.key(),
+            user: user_key,
+            side,
+            lamports,
+            weight,
+        });
+
+        Ok(())
+    }
+
+    /// Clears a commitment that was not revealed in its window: the stake goes to the
+    /// treasury when the round was created with `forfeit_unrevealed`, otherwise back to
+    /// the user less the protocol fee.
+    pub fn forfeit_commitment(ctx: Context<ForfeitCommitment>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let round = &mut ctx.accounts.round;
+        require!(now >= reveal_deadline(round)?, PanchoError::RevealWindowOpen);
+        remove_open_account(round)?;
+
+        let commitment = &ctx.accounts.commitment;
+        let forfeited = round.forfeit_unrevealed;
+        let penalty_lamports = unrevealed_penalty(&ctx.accounts.config, round, commitment.lamports)?;
+        let commitment_info = commitment.to_account_info();
+        transfer_from_vault(&commitment_info, &ctx.accounts.treasury.to_account_info(), penalty_lamports)?;
+        transfer_from_vault(
+            &commitment_info,
+            &ctx.accounts.user.to_account_info(),
+            commitment.lamports - penalty_lamports,
+        )?;
+
+        emit!(CommitmentForfeited {
+            round: commitment.round,
+            user: commitment.user,
+            lamports: commitment.lamports,
+            forfeited,
+            penalty_lamports,
+        });
+
+        Ok(())
+    }
+
+    pub fn withdraw_position(ctx: Context<WithdrawPosition>, lamports: u64) -> Result<()> {
+        require!(!ctx.accounts.config.paused, PanchoError::ProtocolPaused);
+        require!(lamports > 0, PanchoError::InvalidStake);
+
+        let now = Clock::get()?.unix_timestamp;
+        let round = &mut ctx.accounts.round;
+        let position = &mut ctx.accounts.position;
+
+        require!(round.status == ROUND_OPEN, PanchoError::RoundNotOpen);
+        require!(now < round.lock_ts, PanchoError::RoundLocked);
+        require!(lamports <= position.amount, PanchoError::InvalidStake);
+
+        let removed_weight = proportion(lamports, position.weight, position.amount)?;
+        position.weight = position
+            .weight
+            .checked_sub(removed_weight)
+            .ok_or(PanchoError::MathOverflow)?;
+        remove_side_weight(round, position.side, removed_weight)?;
+        position.amount = position
+            .amount
+            .checked_sub(lamports)
+            .ok_or(PanchoError::MathOverflow)?;
+        if position.side == SIDE_UP {
+            round.up_total = round
+                .up_total
+                .checked_sub(lamports)
+                .ok_or(PanchoError::MathOverflow)?;
+        } else {
+            round.down_total = round
+                .down_total
+                .checked_sub(lamports)
+                .ok_or(PanchoError::MathOverflow)?;
+        }
+        if position.amount == 0 {
+            remove_participant(round, position.side)?;
+        }
+
+        let fee_lamports = proportion(
+            lamports,
+            ctx.accounts.config.exit_fee_bps as u64,
+            BPS_DENOMINATOR,
+        )?;
+        let refund_lamports = lamports
+            .checked_sub(fee_lamports)
+            .ok_or(PanchoError::MathOverflow)?;
+
+        record_withdrawal(&mut ctx.accounts.user_stats, lamports, position.amount == 0, now);
+        apply_season_pnl(
+            &ctx.accounts.config,
+            ctx.accounts.season.as_mut(),
+            ctx.accounts.season_stats.as_ref(),
+            None,
+            round,
+            position.user,
+            SeasonPnl::Credit(refund_lamports),
+            now,
+        )?;
+
+        let side_vault = ctx.accounts.side_vault.to_account_info();
+        transfer_from_vault(&side_vault, &ctx.accounts.treasury.to_account_info(), fee_lamports)?;
+        transfer_from_vault(&side_vault, &ctx.accounts.user.to_account_info(), refund_lamports)?;
+
+        emit!(PositionWithdrawn {
+            round: round.key(),
+            user: position.user,
+            side: position.side,
+            lamports,
+            fee_lamports,
+            remaining_stake: position.amount,
+        });
+
+        Ok(())
+    }
+
+    pub fn switch_side(ctx: Context<SwitchSide>, to_side: u8) -> Result<()> {
+        require!(!ctx.accounts.config.paused, PanchoError::ProtocolPaused);
+        require!(to_side == SIDE_UP || to_side == SIDE_DOWN, PanchoError::InvalidSide);
+
+        let now = Clock::get()?.unix_timestamp;
+        let round = &mut ctx.accounts.round;
+        let from_side = to_side ^ 1;
+        let lamports = ctx.accounts.from_position.amount;
+
+        require!(round.status == ROUND_OPEN, PanchoError::RoundNotOpen);
+        require!(now < round.lock_ts, PanchoError::RoundLocked);
+        require!(lamports > 0, PanchoError::InvalidStake);
+
+        if from_side == SIDE_UP {
+            round.up_total = round
+                .up_total
+                .checked_sub(lamports)
+                .ok_or(PanchoError::MathOverflow)?;
+        } else {
+            round.down_total = round
+                .down_total
+                .checked_sub(lamports)
+                .ok_or(PanchoError::MathOverflow)?;
+        }
+        remove_side_weight(round, from_side, ctx.accounts.from_position.weight)?;
+        remove_participant(round, from_side)?;
+        remove_open_account(round)?;
+        ctx.accounts.from_position.amount = 0;
+        ctx.accounts.from_position.weight = 0;
+
+        let fee_lamports = proportion(
+            lamports,
+            ctx.accounts.config.switch_fee_bps as u64,
+            BPS_DENOMINATOR,
+        )?;
+        let moved_lamports = lamports
+            .checked_sub(fee_lamports)
+            .ok_or(PanchoError::MathOverflow)?;
+
+        apply_join(
+            &ctx.accounts.config,
+            round,
+            &mut ctx.accounts.to_position,
+            JoinStake {
+                user: ctx.accounts.user.key(),
+                side: to_side,
+                lamports: moved_lamports,
+                position_bump: ctx.bumps.to_position,
+                min_multiplier_bps: None,
+            },
+            now,
+        )?;
+
+        // Only the switch fee leaves the stake; the rest moves across unchanged.
+        record_withdrawal(&mut ctx.accounts.user_stats, fee_lamports, false, now);
+
+        let from_vault = ctx.accounts.from_vault.to_account_info();
+        transfer_from_vault(&from_vault, &ctx.accounts.treasury.to_account_info(), fee_lamports)?;
+        transfer_from_vault(&from_vault, &ctx.accounts.to_vault.to_account_info(), moved_lamports)?;
+
+        emit!(SideSwitched {
+            round: round.key(),
+            user: ctx.accounts.user.key(),
+            from_side,
+            to_side,
+            lamports: moved_lamports,
+            fee_lamports,
+        });
+
+        Ok(())
+    }
+
+    pub fn lock_round(ctx: Context<LockRound>) -> Result<()> {
+        require!(!ctx.accounts.config.paused, PanchoError::ProtocolPaused);
+
+        let now = Clock::get()?.unix_timestamp;
+        let round = &mut ctx.accounts.round;
+
+        require!(round.status == ROUND_OPEN, PanchoError::RoundAlreadyLocked);
+        require!(now >= round.lock_ts, PanchoError::TooEarlyToLock);
+        require!(now <= round.lock_ts + LOCK_GRACE_SECONDS, PanchoError::LockWindowExpired);
+
+        let price = read_oracle_price(
+            &ctx.accounts.config,
+            &ctx.accounts.oracle_price,
+            OracleRead {
+                kind: round.oracle_kind,
+                account: round.oracle_price_account,
+                feed_id: round.feed_id,
+                min_publish_ts: round.lock_ts,
+                max_publish_ts: round.lock_ts + ORACLE_PUBLISH_TOLERANCE_SECONDS,
+            },
+        )?;
+
+        round.start_price = price.price;
+        round.expo = price.expo;
+        round.status = ROUND_LOCKED;
+
+        seed_house_liquidity(
+            &mut ctx.accounts.house_vault,
+            round,
+            ctx.accounts.house_position.as_ref(),
+            ctx.accounts.house_side_vault.as_ref(),
+            ctx.accounts.system_program.as_ref(),
+            ctx.program_id,
+            now,
+        )?;
+
+        emit!(RoundLocked {
+            round: round.key(),
+            start_price: round.start_price,
+            expo: round.expo,
+            locked_at: now,
+        });
+
+        Ok(())
+    }
+
+    pub fn settle_round(ctx: Context<SettleRound>) -> Result<()> {
+        require!(!ctx.accounts.config.paused, PanchoError::ProtocolPaused);
+
+        let now = Clock::get()?.unix_timestamp;
+        let round = &mut ctx.accounts.round;
+
+        require!(now >= round.end_ts, PanchoError::TooEarlyToSettle);
+        require!(round.status != ROUND_SETTLED, PanchoError::RoundAlreadySettled);
+
+        if round.status == ROUND_OPEN {
+            round.status = ROUND_SETTLED;
+            round.winner_side = SIDE_NONE;
+        }
+
+        // Once no update published inside the settle window can pass the age check, the
+        // round can never read an end price, so it settles void.
+        if round.status == ROUND_LOCKED && settle_read_expired(&ctx.accounts.config, round, now) {
+            round.status = ROUND_SETTLED;
+            round.winner_side = SIDE_NONE;
+        }
+
+        if round.status == ROUND_LOCKED {
+            let price = read_oracle_price(
+                &ctx.accounts.config,
+                &ctx.accounts.oracle_price,
+                OracleRead {
+                    kind: round.oracle_kind,
+                    account: round.oracle_price_account,
+                    feed_id: round.feed_id,
+                    min_publish_ts: round.end_ts,
+                    max_publish_ts: round.end_ts + ORACLE_PUBLISH_TOLERANCE_SECONDS,
+                },
+            )?;
+            round.end_price = price.price;
+
+            let config = &ctx.accounts.config;
+            let below_participation = round.up_count < config.min_side_participants
+                || round.down_count < config.min_side_participants
+                || round.up_total.saturating_add(round.down_total) < config.min_pool_lamports;
+
+            if round.up_total == 0
+                || round.down_total == 0
+                || round.start_price == round.end_price
+                || below_participation
+            {
+                round.winner_side = SIDE_NONE;
+            } else if round.end_price > round.start_price {
+                round.winner_side = SIDE_UP;
+            } else {
+                round.winner_side = SIDE_DOWN;
+            }
+
+            round.status = ROUND_SETTLED;
+        }
+        round.settled_ts = now;
+
+        // Payouts are computed on the gross pool apart from a room host's share, which
+        // leaves the vaults here. A share that would leave the host below rent exemption
+        // would fail the settlement, so it stays in the pool instead.
+        let gross_lamports = round
+            .up_total
+            .checked_add(round.down_total)
+            .ok_or(PanchoError::MathOverflow)?;
+        let mut host_fee = if round.room != Pubkey::default() && round.winner_side != SIDE_NONE {
+            proportion(round.host_fee_bps as u64, gross_lamports, BPS_DENOMINATOR)?
+        } else {
+            0
+        };
+        if host_fee > 0 {
+            let room_host = ctx
+                .accounts
+                .room_host
+                .as_ref()
+                .ok_or(PanchoError::RoomHostRequired)?;
+            require_keys_eq!(room_host.key(), round.room_host, PanchoError::RoomMismatch);
+            let balance = room_host
+                .lamports()
+                .checked_add(host_fee)
+                .ok_or(PanchoError::MathOverflow)?;
+            if Rent::get()?.is_exempt(balance, room_host.data_len()) {
+                transfer_from_vaults(
+                    &ctx.accounts.up_vault.to_account_info(),
+                    &ctx.accounts.down_vault.to_account_info(),
+                    &room_host.to_account_info(),
+                    host_fee,
+                )?;
+                emit!(RoomHostPaid {
+                    round: round.key(),
+                    room: round.room,
+                    host: round.room_host,
+                    lamports: host_fee,
+                });
+            } else {
+                host_fee = 0;
+            }
+        }
+        round.host_fee_lamports = host_fee;
+        round.fee_lamports = 0;
+        round.distributable_lamports = gross_lamports - host_fee;
+
+        // Every winner pays at least the lowest fee rate at claim, so that slice of the
+        // pool is charged here: insurance and the fee split are paid now, and the crank
+        // tip and referral shares stay in the vaults for claims. Tier fees above that
+        // rate are retained at claim and go through the split at `close_round`.
+        let config = &ctx.accounts.config;
+        let winner_count = if round.winner_side == SIDE_UP {
+            round.up_count
+        } else {
+            round.down_count
+        };
+        // A winning side without players is house liquidity, which pays no claim fee.
+        round.settled_fee_bps = if round.winner_side != SIDE_NONE && winner_count > 0 {
+            min_claim_fee_bps(config)
+        } else {
+            0
+        };
+        let settled_fee = proportion(
+            round.settled_fee_bps as u64,
+            round.distributable_lamports,
+            BPS_DENOMINATOR,
+        )?;
+        let claim_reserve = proportion(
+            config.crank_tip_bps as u64 + config.referral_fee_bps as u64,
+            settled_fee,
+            BPS_DENOMINATOR,
+        )?;
+        let insurance_lamports = proportion(config.insurance_fee_bps as u64, settled_fee, BPS_DENOMINATOR)?;
+
+        let up_vault = ctx.accounts.up_vault.to_account_info();
+        let down_vault = ctx.accounts.down_vault.to_account_info();
+        if insurance_lamports > 0 {
+            let insurance_fund = ctx
+                .accounts
+                .insurance_fund
+                .as_mut()
+                .ok_or(PanchoError::InsuranceFundRequired)?;
+            deposit_to_insurance(insurance_fund, &up_vault, &down_vault, round.key(), insurance_lamports)?;
+        }
+        let protocol_fee = settled_fee
+            .checked_sub(claim_reserve)
+            .and_then(|fee| fee.checked_sub(insurance_lamports))
+            .ok_or(PanchoError::MathOverflow)?;
+        distribute_protocol_fee(
+            config,
+            round.key(),
+            &up_vault,
+            &down_vault,
+            &ctx.accounts.treasury.to_account_info(),
+            ctx.remaining_accounts,
+            protocol_fee,
+        )?;
+        round.settled_fee_lamports = insurance_lamports + protocol_fee;
+
+        emit!(RoundSettled {
+            round: round.key(),
+            winner_side: round.winner_side,
+            start_price: round.start_price,
+            end_price: round.end_price,
+            fee_lamports: settled_fee,
+            distributable_lamports: round.distributable_lamports,
+            settled_at: now,
+        });
+
+        Ok(())
+    }
+
+    pub fn claim(ctx: Context<Claim>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let config = &ctx.accounts.config;
+        let round = &mut ctx.accounts.round;
+        let position = &mut ctx.accounts.position;
+
+        let fee_bps = claim_fee_bps(config, &ctx.accounts.user_stats, now);
+        let (payout, fee) = apply_claim(round, position, fee_bps)?;
+        record_claim_result(&mut ctx.accounts.user_stats, round, position, payout)?;
+        apply_season_pnl(
+            config,
+            ctx.accounts.season.as_mut(),
+            ctx.accounts.season_stats.as_ref(),
+            None,
+            round,
+            position.user,
+            SeasonPnl::Credit(payout),
+            now,
+        )?;
+
+        let up_vault = ctx.accounts.up_vault.to_account_info();
+        let down_vault = ctx.accounts.down_vault.to_account_info();
+        if payout > 0 {
+            transfer_from_vaults(&up_vault, &down_vault, &ctx.accounts.user.to_account_info(), payout)?;
+        }
+        pay_referral_share(
+            config,
+            round,
+            position,
+            fee,
+            &ctx.accounts.referral.to_account_info(),
+            ctx.accounts.referrer_rewards.as_mut(),
+            &up_vault,
+            &down_vault,
+        )?;
+
+        Ok(())
+    }
+
+    /// Permissionless claim: the payout always goes to `position.user`, and the caller
+    /// earns a tip out of the crank share of the position's fee.
+    pub fn claim_for(ctx: Context<ClaimFor>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let config = &ctx.accounts.config;
+        let round = &mut ctx.accounts.round;
+        let position = &mut ctx.accounts.position;
+
+        let fee_bps = claim_fee_bps(config, &ctx.accounts.user_stats, now);
+        let (payout, fee) = apply_claim(round, position, fee_bps)?;
+        record_claim_result(&mut ctx.accounts.user_stats, round, position, payout)?;
+        apply_season_pnl(
+            config,
+            ctx.accounts.season.as_mut(),
+            ctx.accounts.season_stats.as_ref(),
+            None,
+            round,
+            position.user,
+            SeasonPnl::Credit(payout),
+            now,
+        )?;
+        let tip = config
+            .crank_tip_lamports
+            .min(proportion(config.crank_tip_bps as u64, fee, BPS_DENOMINATOR)?);
+        round.tips_paid_lamports = round
+            .tips_paid_lamports
+            .checked_add(tip)
+            .ok_or(PanchoError::MathOverflow)?;
+
+        let up_vault = ctx.accounts.up_vault.to_account_info();
+        let down_vault = ctx.accounts.down_vault.to_account_info();
+        transfer_from_vaults(&up_vault, &down_vault, &ctx.accounts.user.to_account_info(), payout)?;
+        pay_referral_share(
+            config,
+            round,
+            position,
+            fee,
+            &ctx.accounts.referral.to_account_info(),
+            ctx.accounts.referrer_rewards.as_mut(),
+            &up_vault,
+            &down_vault,
+        )?;
+
+        if tip > 0 {
+            transfer_from_vaults(&up_vault, &down_vault, &ctx.accounts.cranker.to_account_info(), tip)?;
+            emit!(CrankTipPaid {
+                round: round.key(),
+                user: position.user,
+                cranker: ctx.accounts.cranker.key(),
+                tip_lamports: tip,
+            });
+        }
+
+        Ok(())
+    }
+
+    pub fn claim_house(ctx: Context<ClaimHouse>) -> Result<()> {
+        let round = &mut ctx.accounts.round;
+        let position = &mut ctx.accounts.position;
+
+        // The house is protocol liquidity, so it pays no claim fee.
+        let (payout, _) = apply_claim(round, position, 0)?;
+        remove_open_account(round)?;
+        transfer_from_vaults(
+            &ctx.accounts.up_vault.to_account_info(),
+            &ctx.accounts.down_vault.to_account_info(),
+            &ctx.accounts.house_vault.to_account_info(),
+            payout,
+        )?;
+
+        let market = market_index(round.market)?;
+        let pnl = i64::try_from(payout as i128 - position.amount as i128)
+            .map_err(|_| error!(PanchoError::MathOverflow))?;
+        let house_vault = &mut ctx.accounts.house_vault;
+        house_vault.returned_lamports[market] = house_vault.returned_lamports[market]
+            .checked_add(payout)
+            .ok_or(PanchoError::MathOverflow)?;
+        house_vault.pnl_lamports[market] = house_vault.pnl_lamports[market]
+            .checked_add(pnl)
+            .ok_or(PanchoError::MathOverflow)?;
+
+        emit!(HouseSettled {
+            round: round.key(),
+            market: round.market,
+            stake: position.amount,
+            payout,
+            market_pnl_lamports: house_vault.pnl_lamports[market],
+        });
+
+        Ok(())
+    }
+
+    /// The owner closes a claimed or losing position; once the claim window is over
+    /// anyone can close any position, returning the rent to `position.user`, so
+    /// abandoned positions cannot hold up `close_round`.
+    pub fn close_position(ctx: Context<ClosePosition>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let round = &mut ctx.accounts.round;
+        let position = &ctx.accounts.position;
+
+        require!(round.status == ROUND_SETTLED, PanchoError::RoundNotSettled);
+        let payout = position_payout(round, position)?;
+        let claim_deadline = round
+            .settled_ts
+            .checked_add(ctx.accounts.config.claim_window_seconds)
+            .ok_or(PanchoError::MathOverflow)?;
+        if now < claim_deadline {
+            require_keys_eq!(
+                ctx.accounts.closer.key(),
+                position.user,
+                PanchoError::PositionUserMismatch
+            );
+            require!(position.claimed || payout == 0, PanchoError::PositionNotClaimed);
+        }
+        // Losers can close without claiming; count the loss here in that case.
+        if !position.claimed && payout == 0 {
+            record_claim_result(&mut ctx.accounts.user_stats, round, position, 0)?;
+        }
+        remove_open_account(round)?;
+
+        emit!(PositionClosed {
+            round: round.key(),
+            user: position.user,
+            side: position.side,
+            stake: position.amount,
+            claimed: position.claimed,
+            rent_lamports: position.to_account_info().lamports(),
+        });
+
+        Ok(())
+    }
+
+    /// Read-only view for other programs: returns a snapshot of the user's stats,
+    /// including the rolling 30-day volume as of now, via return data.
+    pub fn get_user_stats(ctx: Context<GetUserStats>) -> Result<UserStatsSnapshot> {
+        let now = Clock::get()?.unix_timestamp;
+        let user_stats = &ctx.accounts.user_stats;
+        Ok(UserStatsSnapshot {
+            user: user_stats.user,
+            volume_30d_lamports: rolling_volume(user_stats, now),
+            total_staked_lamports: user_stats.total_staked_lamports,
+            total_payout_lamports: user_stats.total_payout_lamports,
+            rounds_played: user_stats.rounds_played,
+            wins: user_stats.wins,
+            losses: user_stats.losses,
+            voids: user_stats.voids,
+            current_streak: user_stats.current_streak,
+            best_streak: user_stats.best_streak,
+        })
+    }
+
+    pub fn close_round(ctx: Context<CloseRound>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let round = &ctx.accounts.round;
+
+        require!(round.status == ROUND_SETTLED, PanchoError::RoundNotSettled);
+        let claim_deadline = round
+            .settled_ts
+            .checked_add(ctx.accounts.config.claim_window_seconds)
+            .ok_or(PanchoError::MathOverflow)?;
+        require!(now >= claim_deadline, PanchoError::ClaimWindowOpen);
+        require!(round.open_accounts == 0, PanchoError::RoundHasOpenAccounts);
+
+        let up_vault = ctx.accounts.up_vault.to_account_info();
+        let down_vault = ctx.accounts.down_vault.to_account_info();
+        let rent = Rent::get()?;
+        let swept_lamports = up_vault
+            .lamports()
+            .saturating_sub(rent.minimum_balance(up_vault.data_len()))
+            .checked_add(
+                down_vault
+                    .lamports()
+                    .saturating_sub(rent.minimum_balance(down_vault.data_len())),
+            )
+            .ok_or(PanchoError::MathOverflow)?;
+
+        // Claim fees net of tips and referral shares are still sitting in the vaults, less
+        // what settle already paid out at the round's minimum rate; the rest goes to
+        // insurance and the fee split in the same proportions, and anything else is
+        // unclaimed stake.
+        let config = &ctx.accounts.config;
+        let protocol_fee = round
+            .fee_lamports
+            .checked_sub(round.tips_paid_lamports)
+            .and_then(|fee| fee.checked_sub(round.referral_paid_lamports))
+            .ok_or(PanchoError::MathOverflow)?
+            .saturating_sub(round.settled_fee_lamports)
+            .min(swept_lamports);
+        let net_fee_bps = BPS_DENOMINATOR
+            .saturating_sub(config.crank_tip_bps as u64 + config.referral_fee_bps as u64);
+        let insurance_lamports =
+            proportion(config.insurance_fee_bps as u64, protocol_fee, net_fee_bps)?.min(protocol_fee);
+        if insurance_lamports > 0 {
+            let insurance_fund = ctx
+                .accounts
+                .insurance_fund
+                .as_mut()
+                .ok_or(PanchoError::InsuranceFundRequired)?;
+            deposit_to_insurance(insurance_fund, &up_vault, &down_vault, round.key(), insurance_lamports)?;
+        }
+        distribute_protocol_fee(
+            &ctx.accounts.config,
+            round.key(),
+            &up_vault,
+            &down_vault,
+            &ctx.accounts.treasury.to_account_info(),
+            ctx.remaining_accounts,
+            protocol_fee - insurance_lamports,
+        )?;
+
+        let unclaimed_lamports = swept_lamports - protocol_fee;
+        if ctx.accounts.config.sweep_to_insurance {
+            let insurance_fund = ctx
+                .accounts
+                .insurance_fund
+                .as_mut()
+                .ok_or(PanchoError::InsuranceFundRequired)?;
+            deposit_to_insurance(insurance_fund, &up_vault, &down_vault, round.key(), unclaimed_lamports)?;
+        } else {
+            transfer_from_vaults(
+                &up_vault,
+                &down_vault,
+                &ctx.accounts.treasury.to_account_info(),
+                unclaimed_lamports,
+            )?;
+        }
+
+        emit!(RoundClosed {
+            round: round.key(),
+            swept_lamports,
+            protocol_fee_lamports: protocol_fee,
+            rent_payer: round.rent_payer,
+            closed_at: now,
+        });
+
+        Ok(())
+    }
+
+    /// Opens a 1v1 duel and escrows the challenger's stake in the duel PDA.
+    pub fn create_duel(
+        ctx: Context<CreateDuel>,
+        duel_id: u64,
+        market: u8,
+        side: u8,
+        stake_lamports: u64,
+        expiry_ts: i64,
+        duration_seconds: i64,
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.paused, PanchoError::ProtocolPaused);
+        require!(stake_lamports > 0, PanchoError::InvalidStake);
+        require!(side == SIDE_UP || side == SIDE_DOWN, PanchoError::InvalidSide);
+        market_index(market)?;
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            expiry_ts > now && duration_seconds > 0 && duration_seconds <= MAX_DUEL_DURATION_SECONDS,
+            PanchoError::InvalidSchedule
+        );
+
+        let cpi_accounts = anchor_lang::system_program::Transfer {
+            from: ctx.accounts.challenger.to_account_info(),
+            to: ctx.accounts.duel.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        anchor_lang::system_program::transfer(cpi_ctx, stake_lamports)?;
+
+        let duel = &mut ctx.accounts.duel;
+        duel.challenger = ctx.accounts.challenger.key();
+        duel.counterparty = Pubkey::default();
+        duel.duel_id = duel_id;
+        duel.market = market;
+        duel.challenger_side = side;
+        duel.stake_lamports = stake_lamports;
+        duel.expiry_ts = expiry_ts;
+        duel.duration_seconds = duration_seconds;
+        duel.status = DUEL_OPEN;
+        duel.start_price = 0;
+        duel.expo = 0;
+        duel.lock_ts = 0;
+        duel.end_ts = 0;
+        duel.bump = ctx.bumps.duel;
+        duel.oracle_kind = ORACLE_KIND_LEGACY;
+
+        emit!(DuelCreated {
+            duel: duel.key(),
+            challenger: duel.challenger,
+            market,
+            side,
+            stake_lamports,
+            expiry_ts,
+            duration_seconds,
+        });
+
+        Ok(())
+    }
+
+    /// Takes the opposite side with the same stake. The start price is read afterwards by
+    /// `lock_duel`, so it cannot predate the acceptance.
+    pub fn accept_duel(ctx: Context<AcceptDuel>, stake_lamports: u64) -> Result<()> {
+        require!(!ctx.accounts.config.paused, PanchoError::ProtocolPaused);
+
+        let now = Clock::get()?.unix_timestamp;
+        let duel = &ctx.accounts.duel;
+        require!(duel.status == DUEL_OPEN, PanchoError::DuelNotOpen);
+        require!(now < duel.expiry_ts, PanchoError::DuelExpired);
+        require!(stake_lamports == duel.stake_lamports, PanchoError::InvalidStake);
+        require_keys_neq!(
+            ctx.accounts.counterparty.key(),
+            duel.challenger,
+            PanchoError::InvalidDuelCounterparty
+        );
+        let oracle_kind = ctx.accounts.config.oracle_kinds[market_index(duel.market)?];
+
+        let cpi_accounts = anchor_lang::system_program::Transfer {
+            from: ctx.accounts.counterparty.to_account_info(),
+            to: ctx.accounts.duel.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        anchor_lang::system_program::transfer(cpi_ctx, stake_lamports)?;
+
+        let duel = &mut ctx.accounts.duel;
+        duel.counterparty = ctx.accounts.counterparty.key();
+        duel.status = DUEL_ACCEPTED;
+        duel.oracle_kind = oracle_kind;
+        duel.lock_ts = now;
+        duel.end_ts = now
+            .checked_add(duel.duration_seconds)
+            .ok_or(PanchoError::MathOverflow)?;
+
+        emit!(DuelAccepted {
+            duel: duel.key(),
+            counterparty: duel.counterparty,
+            lock_ts: duel.lock_ts,
+            end_ts: duel.end_ts,
+        });
+
+        Ok(())
+    }
+
+    /// Permissionless: records the start price from an update published within
+    /// `ORACLE_PUBLISH_TOLERANCE_SECONDS` after acceptance.
+    pub fn lock_duel(ctx: Context<LockDuel>) -> Result<()> {
+        require!(!ctx.accounts.config.paused, PanchoError::ProtocolPaused);
+
+        let now = Clock::get()?.unix_timestamp;
+        let duel = &ctx.accounts.duel;
+        require!(duel.status == DUEL_ACCEPTED, PanchoError::DuelNotAccepted);
+        require!(now <= duel.lock_ts + LOCK_GRACE_SECONDS, PanchoError::LockWindowExpired);
+
+        let price = read_oracle_price(
+            &ctx.accounts.config,
+            &ctx.accounts.oracle_price,
+            duel_oracle_read(&ctx.accounts.config, duel, duel.lock_ts)?,
+        )?;
+
+        let duel = &mut ctx.accounts.duel;
+        duel.status = DUEL_LOCKED;
+        duel.start_price = price.price;
+        duel.expo = price.expo;
+
+        emit!(DuelLocked {
+            duel: duel.key(),
+            start_price: duel.start_price,
+            expo: duel.expo,
+            end_ts: duel.end_ts,
+        });
+
+        Ok(())
+    }
+
+    /// Settles a locked duel from the oracle and closes it to the challenger. The winner
+    /// takes both stakes minus `fee_bps`; an unchanged price refunds both sides fee-free.
+    pub fn settle_duel(ctx: Context<SettleDuel>) -> Result<()> {
+        require!(!ctx.accounts.config.paused, PanchoError::ProtocolPaused);
+
+        let now = Clock::get()?.unix_timestamp;
+        let duel = &ctx.accounts.duel;
+        require!(duel.status == DUEL_LOCKED, PanchoError::DuelNotLocked);
+        require!(now >= duel.end_ts, PanchoError::TooEarlyToSettle);
+        require!(
+            now <= duel.end_ts + DUEL_SETTLE_GRACE_SECONDS,
+            PanchoError::DuelSettleWindowExpired
+        );
+
+        let price = read_oracle_price(
+            &ctx.accounts.config,
+            &ctx.accounts.oracle_price,
+            duel_oracle_read(&ctx.accounts.config, duel, duel.end_ts)?,
+        )?;
+
+        let duel_info = duel.to_account_info();
+        let challenger = ctx.accounts.challenger.to_account_info();
+        let counterparty = ctx.accounts.counterparty.to_account_info();
+        let pot = duel
+            .stake_lamports
+            .checked_mul(2)
+            .ok_or(PanchoError::MathOverflow)?;
+
+        let (winner, payout, fee) = if price.price == duel.start_price {
+            transfer_from_vault(&duel_info, &counterparty, duel.stake_lamports)?;
+            transfer_from_vault(&duel_info, &challenger, duel.stake_lamports)?;
+            (Pubkey::default(), 0, 0)
+        } else {
+            let up_won = price.price > duel.start_price;
+            let challenger_won = up_won == (duel.challenger_side == SIDE_UP);
+            let winner_info = if challenger_won { &challenger } else { &counterparty };
+            let fee = proportion(ctx.accounts.config.fee_bps as u64, pot, BPS_DENOMINATOR)?;
+            let payout = pot - fee;
+            transfer_from_vault(&duel_info, winner_info, payout)?;
+            transfer_from_vault(&duel_info, &ctx.accounts.treasury.to_account_info(), fee)?;
+            (winner_info.key(), payout, fee)
+        };
+
+        emit!(DuelSettled {
+            duel: duel.key(),
+            winner,
+            start_price: duel.start_price,
+            end_price: price.price,
+            payout,
+            fee,
+            settled_at: now,
+        });
+
+        Ok(())
+    }
+
+    /// Refunds an unaccepted duel after its expiry and closes it to the challenger.
+    pub fn cancel_duel(ctx: Context<CancelDuel>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let duel = &ctx.accounts.duel;
+        require!(duel.status == DUEL_OPEN, PanchoError::DuelNotOpen);
+        require!(now >= duel.expiry_ts, PanchoError::DuelNotExpired);
+
+        emit!(DuelCancelled {
+            duel: duel.key(),
+            challenger: duel.challenger,
+            refunded_lamports: duel.stake_lamports,
+        });
+
+        Ok(())
+    }
+
+    /// Permissionless: refunds both stakes of an accepted duel that was never locked, or
+    /// a locked duel that was never settled, once its oracle window has passed.
+    pub fn refund_duel(ctx: Context<RefundDuel>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let duel = &ctx.accounts.duel;
+        require!(duel_refundable(duel, now), PanchoError::DuelNotRefundable);
+
+        let duel_info = duel.to_account_info();
+        transfer_from_vault(&duel_info, &ctx.accounts.counterparty.to_account_info(), duel.stake_lamports)?;
+        transfer_from_vault(&duel_info, &ctx.accounts.challenger.to_account_info(), duel.stake_lamports)?;
+
+        emit!(DuelRefunded {
+            duel: duel.key(),
+            challenger: duel.challenger,
+            counterparty: duel.counterparty,
+            refunded_lamports: duel.stake_lamports,
+        });
+
+        Ok(())
+    }
+
+    /// Posts fixed odds on an open round: takers backing `side` are paid `odds_bps` of
+    /// their stake if it wins. `lamports` is the maker's escrowed liability.
+    pub fn post_offer(
+        ctx: Context<PostOffer>,
+        offer_id: u64,
+        side: u8,
+        odds_bps: u32,
+        lamports: u64,
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.paused, PanchoError::ProtocolPaused);
+        require!(lamports > 0, PanchoError::InvalidStake);
+        require!(side == SIDE_UP || side == SIDE_DOWN, PanchoError::InvalidSide);
+        require!(
+            odds_bps as u64 > BPS_DENOMINATOR && odds_bps <= MAX_OFFER_ODDS_BPS,
+            PanchoError::InvalidOdds
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let round = &mut ctx.accounts.round;
+        require!(round.room == Pubkey::default(), PanchoError::RoomRequired);
+        require!(round.status == ROUND_OPEN, PanchoError::RoundNotOpen);
+        require!(now < round.entry_cutoff_ts, PanchoError::EntryCutoffPassed);
+        add_open_account(round)?;
+        let round_key = round.key();
+
+        let cpi_accounts = anchor_lang::system_program::Transfer {
+            from: ctx.accounts.maker.to_account_info(),
+            to: ctx.accounts.offer.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        anchor_lang::system_program::transfer(cpi_ctx, lamports)?;
+
+        let offer = &mut ctx.accounts.offer;
+        offer.round = round_key;
+        offer.maker = ctx.accounts.maker.key();
+        offer.offer_id = offer_id;
+        offer.side = side;
+        offer.odds_bps = odds_bps;
+        offer.maker_lamports = lamports;
+        offer.remaining_lamports = lamports;
+        offer.fill_count = 0;
+        offer.bump = ctx.bumps.offer;
+
+        emit!(OfferPosted {
+            offer: offer.key(),
+            round: offer.round,
+            maker: offer.maker,
+            side,
+            odds_bps,
+            lamports,
+        });
+
+        Ok(())
+    }
+
+    /// Fills an offer with `lamports` of taker stake. The stake and the matching slice of
+    /// maker liability move into a new `Contract` PDA.
+    pub fn fill_offer(ctx: Context<FillOffer>, lamports: u64) -> Result<()> {
+        require!(!ctx.accounts.config.paused, PanchoError::ProtocolPaused);
+        require!(lamports > 0, PanchoError::InvalidStake);
+
+        let now = Clock::get()?.unix_timestamp;
+        let round = &mut ctx.accounts.round;
+        require!(round.status == ROUND_OPEN, PanchoError::RoundNotOpen);
+        require!(now < round.entry_cutoff_ts, PanchoError::EntryCutoffPassed);
+        add_open_account(round)?;
+
+        let offer = &mut ctx.accounts.offer;
+        require_keys_neq!(ctx.accounts.taker.key(), offer.maker, PanchoError::InvalidOfferTaker);
+        let maker_lamports = proportion(
+            lamports,
+            offer.odds_bps as u64 - BPS_DENOMINATOR,
+            BPS_DENOMINATOR,
+        )?;
+        require!(maker_lamports > 0, PanchoError::InvalidStake);
+        offer.remaining_lamports = offer
+            .remaining_lamports
+            .checked_sub(maker_lamports)
+            .ok_or(PanchoError::OfferLiquidityExceeded)?;
+        offer.fill_count = offer
+            .fill_count
+            .checked_add(1)
+            .ok_or(PanchoError::MathOverflow)?;
+        transfer_from_vault(
+            &offer.to_account_info(),
+            &ctx.accounts.contract.to_account_info(),
+            maker_lamports,
+        )?;
+
+        let cpi_accounts = anchor_lang::system_program::Transfer {
+            from: ctx.accounts.taker.to_account_info(),
+            to: ctx.accounts.contract.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        anchor_lang::system_program::transfer(cpi_ctx, lamports)?;
+
+        let contract = &mut ctx.accounts.contract;
+        contract.offer = offer.key();
+        contract.round = offer.round;
+        contract.maker = offer.maker;
+        contract.taker = ctx.accounts.taker.key();
+        contract.taker_side = offer.side;
+        contract.taker_lamports = lamports;
+        contract.maker_lamports = maker_lamports;
+        contract.bump = ctx.bumps.contract;
+
+        emit!(OfferFilled {
+            offer: offer.key(),
+            contract: contract.key(),
+            taker: contract.taker,
+            taker_lamports: lamports,
+            maker_lamports,
+            remaining_lamports: offer.remaining_lamports,
+        });
+
+        Ok(())
+    }
+
+    /// Pays out a fixed-odds contract from the round's lock and settle prices and closes
+    /// it to the taker. Contracts do not depend on the pool, so a round voided only for
+    /// participation still resolves them; an unlocked round or an unchanged price refunds
+    /// both legs.
+    pub fn settle_contract(ctx: Context<SettleContract>) -> Result<()> {
+        let round = &mut ctx.accounts.round;
+        require!(round.status == ROUND_SETTLED, PanchoError::RoundNotSettled);
+        remove_open_account(round)?;
+        let winner_side = price_winner_side(round);
+
+        let contract = &ctx.accounts.contract;
+        let contract_info = contract.to_account_info();
+        let maker = ctx.accounts.maker.to_account_info();
+        let taker = ctx.accounts.taker.to_account_info();
+        let total = contract
+            .taker_lamports
+            .checked_add(contract.maker_lamports)
+            .ok_or(PanchoError::MathOverflow)?;
+
+        let (taker_payout, maker_payout) = if winner_side == SIDE_NONE {
+            (contract.taker_lamports, contract.maker_lamports)
+        } else if winner_side == contract.taker_side {
+            (total, 0)
+        } else {
+            (0, total)
+        };
+        transfer_from_vault(&contract_info, &taker, taker_payout)?;
+        transfer_from_vault(&contract_info, &maker, maker_payout)?;
+
+        emit!(ContractSettled {
+            contract: contract.key(),
+            round: round.key(),
+            winner_side,
+            taker_payout,
+            maker_payout,
+        });
+
+        Ok(())
+    }
+
+    /// Returns the unfilled part of an offer to the maker once the round has locked.
+    pub fn close_offer(ctx: Context<CloseOffer>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let round = &mut ctx.accounts.round;
+        require!(
+            round.status != ROUND_OPEN || now >= round.lock_ts,
+            PanchoError::OfferStillOpen
+        );
+        remove_open_account(round)?;
+
+        let offer = &ctx.accounts.offer;
+        emit!(OfferClosed {
+            offer: offer.key(),
+            maker: offer.maker,
+            refunded_lamports: offer.remaining_lamports,
+        });
+
+        Ok(())
+    }
+
+    pub fn set_referrer(ctx: Context<SetReferrer>, referrer: Pubkey) -> Result<()> {
+        let user = ctx.accounts.user.key();
+        require_keys_neq!(referrer, user, PanchoError::InvalidReferrer);
+
+        let referral = &mut ctx.accounts.referral;
+        referral.user = user;
+        referral.referrer = referrer;
+        referral.bump = ctx.bumps.referral;
+
+        let referrer_rewards = &mut ctx.accounts.referrer_rewards;
+        if referrer_rewards.referrer == Pubkey::default() {
+            referrer_rewards.referrer = referrer;
+            referrer_rewards.accrued_lamports = 0;
+            referrer_rewards.total_earned_lamports = 0;
+            referrer_rewards.bump = ctx.bumps.referrer_rewards;
+        }
+
+        emit!(ReferralSet { user, referrer });
+
+        Ok(())
+    }
+
+    pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+        let referrer_rewards = &mut ctx.accounts.referrer_rewards;
+        let lamports = referrer_rewards.accrued_lamports;
+        require!(lamports > 0, PanchoError::NothingToClaim);
+
+        referrer_rewards.accrued_lamports = 0;
+        transfer_from_vault(
+            &referrer_rewards.to_account_info(),
+            &ctx.accounts.referrer.to_account_info(),
+            lamports,
+        )?;
+
+        emit!(ReferralRewardsClaimed {
+            referrer: ctx.accounts.referrer.key(),
+            lamports,
+        });
+
+        Ok(())
+    }
+
+    /// Claims several positions owned by `user` in one instruction. `remaining_accounts`
+    /// holds one `(round, position, up_vault, down_vault)` tuple per position.
+    pub fn claim_many<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimMany<'info>>) -> Result<()> {
+        let remaining = ctx.remaining_accounts;
+        require!(
+            !remaining.is_empty() && remaining.len().is_multiple_of(CLAIM_TUPLE_ACCOUNTS),
+            PanchoError::InvalidBatch
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let config = &ctx.accounts.config;
+        let user_key = ctx.accounts.user.key();
+        let fee_bps = claim_fee_bps(config, &ctx.accounts.user_stats, now);
+        let mut total_payout: u64 = 0;
+
+        for accounts in remaining.chunks_exact(CLAIM_TUPLE_ACCOUNTS) {
+            let (round_info, position_info, up_vault_info, down_vault_info) =
+                (&accounts[0], &accounts[1], &accounts[2], &accounts[3]);
+
+            let mut round: Account<Round> = Account::try_from(round_info)?;
+            let round_key = round.key();
+            let mut position: Account<Position> = Account::try_from(position_info)?;
+            require_keys_eq!(position.round, round_key, PanchoError::PositionRoundMismatch);
+            require_keys_eq!(position.user, user_key, PanchoError::PositionUserMismatch);
+            let expected_position = Pubkey::create_program_address(
+                &[
+                    b"position",
+                    round_key.as_ref(),
+                    user_key.as_ref(),
+                    &[position.side],
+                    &[position.bump],
+                ],
+                ctx.program_id,
+            )
+            .map_err(|_| error!(PanchoError::InvalidPositionAccount))?;
+            require_keys_eq!(
+                position_info.key(),
+                expected_position,
+                PanchoError::InvalidPositionAccount
+            );
+            load_round_vault(up_vault_info, round_key, SIDE_UP, ctx.program_id)?;
+            load_round_vault(down_vault_info, round_key, SIDE_DOWN, ctx.program_id)?;
+
+            let (payout, fee) = apply_claim(&mut round, &mut position, fee_bps)?;
+            record_claim_result(&mut ctx.accounts.user_stats, &round, &position, payout)?;
+            apply_season_pnl(
+                config,
+                ctx.accounts.season.as_mut(),
+                ctx.accounts.season_stats.as_ref(),
+                None,
+                &round,
+                user_key,
+                SeasonPnl::Credit(payout),
+                now,
+            )?;
+            debit_vaults(up_vault_info, down_vault_info, payout)?;
+            pay_referral_share(
+                config,
+                &mut round,
+                &position,
+                fee,
+                &ctx.accounts.referral.to_account_info(),
+                ctx.accounts.referrer_rewards.as_mut(),
+                up_vault_info,
+                down_vault_info,
+            )?;
+            total_payout = total_payout
+                .checked_add(payout)
+                .ok_or(PanchoError::MathOverflow)?;
+
+            // Written back before the next tuple loads, so both sides of one round
+            // can be claimed in the same batch.
+            round.exit(ctx.program_id)?;
+            position.exit(ctx.program_id)?;
+        }
+
+        credit_lamports(&ctx.accounts.user.to_account_info(), total_payout)
+    }
+}
+
+// What a position's claim pays short of its stake, before claim fees.
+fn insurable_shortfall(round: &Round, position: &Position) -> Result<u64> {
+    Ok(position.amount.saturating_sub(position_payout(round, position)?))
+}
+
+fn position_payout(round: &Round, position: &Position) -> Result<u64> {
+    if round.winner_side == SIDE_NONE {
+        proportion(position.amount, round.distributable_lamports, round.up_total + round.down_total)
+    } else if position.side == round.winner_side {
+        let (winner_total, winner_weight) = if round.winner_side == SIDE_UP {
+            (round.up_total, round.up_weight)
+        } else {
+            (round.down_total, round.down_weight)
+        };
+        // A side whose weights all rounded to zero (e.g. a house stake at a zero floor)
+        // splits by stake instead.
+        if winner_weight == 0 {
+            return proportion(position.amount, round.distributable_lamports, winner_total);
+        }
+        proportion(position.weight, round.distributable_lamports, winner_weight)
+    } else {
+        Ok(0)
+    }
+}
+
+// Returns `(payout, fee)`. Winners pay `fee_bps` of their gross payout, never less than
+// the rate settle already paid out for the round; the fee stays in the vaults and is
+// accounted on the round. Refunds from void rounds are fee-free.
+fn apply_claim(
+    round: &mut Account<Round>,
+    position: &mut Account<Position>,
+    fee_bps: u16,
+) -> Result<(u64, u64)> {
+    require!(round.status == ROUND_SETTLED, PanchoError::RoundNotSettled);
+    require!(!position.claimed, PanchoError::AlreadyClaimed);
+    require!(position.amount > 0, PanchoError::NothingToClaim);
+
+    let gross = position_payout(round, position)?;
+    let fee = if round.winner_side == SIDE_NONE {
+        0
+    } else {
+        proportion(fee_bps.max(round.settled_fee_bps) as u64, gross, BPS_DENOMINATOR)?
+    };
+    let payout = gross.checked_sub(fee).ok_or(PanchoError::MathOverflow)?;
+    round.fee_lamports = round
+        .fee_lamports
+        .checked_add(fee)
+        .ok_or(PanchoError::MathOverflow)?;
+    round.claimed_positions = round
+        .claimed_positions
+        .checked_add(1)
+        .ok_or(PanchoError::MathOverflow)?;
+    position.claimed = true;
+
+    emit!(Claimed {
+        round: round.key(),
+        user: position.user,
+        side: position.side,
+        stake: position.amount,
+        payout,
+        fee,
+    });
+
+    Ok((payout, fee))
+}
+
+fn claim_fee_bps(config: &GlobalConfig, user_stats: &UserStats, now: i64) -> u16 {
+    let volume = rolling_volume(user_stats, now);
+    let tiers = config.fee_tier_count as usize;
+    config.fee_tier_volume_lamports[..tiers]
+        .iter()
+        .zip(&config.fee_tier_bps[..tiers])
+        .rev()
+        .find(|(threshold, _)| volume >= **threshold)
+        .map_or(config.fee_bps, |(_, bps)| *bps)
+}
+
+// Lowest rate any winner can be charged at claim: the base rate or the cheapest tier.
+fn min_claim_fee_bps(config: &GlobalConfig) -> u16 {
+    config.fee_tier_bps[..config.fee_tier_count as usize]
+        .iter()
+        .fold(config.fee_bps, |lowest, bps| lowest.min(*bps))
+}
+
+fn record_join(
+    user_stats: &mut Account<UserStats>,
+    user: Pubkey,
+    bump: u8,
+    lamports: u64,
+    new_position: bool,
+    now: i64,
+) -> Result<()> {
+    if user_stats.user == Pubkey::default() {
+        user_stats.user = user;
+        user_stats.bump = bump;
+    }
+    user_stats.total_staked_lamports = user_stats
+        .total_staked_lamports
+        .checked_add(lamports)
+        .ok_or(PanchoError::MathOverflow)?;
+    if new_position {
+        user_stats.rounds_played = user_stats
+            .rounds_played
+            .checked_add(1)
+            .ok_or(PanchoError::MathOverflow)?;
+    }
+
+    let day = now.div_euclid(SECONDS_PER_DAY);
+    let elapsed = day.saturating_sub(user_stats.last_volume_day);
+    if elapsed >= VOLUME_WINDOW_DAYS as i64 {
+        user_stats.daily_volume_lamports = [0; VOLUME_WINDOW_DAYS];
+    } else {
+        for stale_day in user_stats.last_volume_day + 1..=day {
+            user_stats.daily_volume_lamports[volume_bucket(stale_day)] = 0;
+        }
+    }
+    user_stats.last_volume_day = user_stats.last_volume_day.max(day);
+
+    let bucket = &mut user_stats.daily_volume_lamports[volume_bucket(day)];
+    *bucket = bucket.checked_add(lamports).ok_or(PanchoError::MathOverflow)?;
+    Ok(())
+}
+
+// Stake that leaves a round before lock comes back out of the lifetime total and the
+// rolling volume (newest buckets first), so exits cannot farm stats or fee tiers.
+fn record_withdrawal(user_stats: &mut UserStats, lamports: u64, closed_position: bool, now: i64) {
+    user_stats.total_staked_lamports = user_stats.total_staked_lamports.saturating_sub(lamports);
+    if closed_position {
+        user_stats.rounds_played = user_stats.rounds_played.saturating_sub(1);
+    }
+
+    let window_start = now.div_euclid(SECONDS_PER_DAY) - VOLUME_WINDOW_DAYS as i64;
+    let last_day = user_stats.last_volume_day;
+    let mut remaining = lamports;
+    for bucket_day in (last_day - VOLUME_WINDOW_DAYS as i64 + 1..=last_day).rev() {
+        if remaining == 0 || bucket_day <= window_start {
+            break;
+        }
+        let bucket = &mut user_stats.daily_volume_lamports[volume_bucket(bucket_day)];
+        let taken = (*bucket).min(remaining);
+        *bucket -= taken;
+        remaining -= taken;
+    }
+}
+
+// Streaks count consecutive wins; a loss resets the streak and a void leaves it as is.
+fn record_claim_result(
+    user_stats: &mut UserStats,
+    round: &Round,
+    position: &Position,
+    payout: u64,
+) -> Result<()> {
+    user_stats.total_payout_lamports = user_stats
+        .total_payout_lamports
+        .checked_add(payout)
+        .ok_or(PanchoError::MathOverflow)?;
+    if round.winner_side == SIDE_NONE {
+        user_stats.voids = user_stats.voids.checked_add(1).ok_or(PanchoError::MathOverflow)?;
+    } else if position.side == round.winner_side {
+        user_stats.wins = user_stats.wins.checked_add(1).ok_or(PanchoError::MathOverflow)?;
+        user_stats.current_streak = user_stats
+            .current_streak
+            .checked_add(1)
+            .ok_or(PanchoError::MathOverflow)?;
+        user_stats.best_streak = user_stats.best_streak.max(user_stats.current_streak);
+    } else {
+        user_stats.losses = user_stats.losses.checked_add(1).ok_or(PanchoError::MathOverflow)?;
+        user_stats.current_streak = 0;
+    }
+    Ok(())
+}
+
+enum SeasonPnl {
+    Debit(u64),
+    Credit(u64),
+}
+
+// Season PnL is stakes debited at join and payouts credited at claim, so skipping a
+// losing claim cannot hide a loss. While a season is active every join and claim must
+// pass it; only public rounds opened inside the season window count, and the table
+// freezes once the grace period after `end_ts` starts prize claims.
+#[allow(clippy::too_many_arguments)]
+fn apply_season_pnl<'info>(
+    config: &GlobalConfig,
+    season: Option<&mut Account<'info, Season>>,
+    season_stats: Option<&UncheckedAccount<'info>>,
+    creator: Option<(&AccountInfo<'info>, &AccountInfo<'info>)>,
+    round: &Round,
+    user: Pubkey,
+    pnl: SeasonPnl,
+    now: i64,
+) -> Result<()> {
+    if config.active_season == Pubkey::default() || round.room != Pubkey::default() {
+        return Ok(());
+    }
+    let season = season.ok_or(PanchoError::SeasonAccountsRequired)?;
+    require_keys_eq!(season.key(), config.active_season, PanchoError::SeasonMismatch);
+
+    let delta = match pnl {
+        SeasonPnl::Debit(lamports) => -i64::try_from(lamports).map_err(|_| error!(PanchoError::MathOverflow))?,
+        SeasonPnl::Credit(lamports) => i64::try_from(lamports).map_err(|_| error!(PanchoError::MathOverflow))?,
+    };
+    let in_window = round.open_ts >= season.start_ts && round.open_ts < season.end_ts;
+    if delta == 0 || !in_window || now >= ranking_final_ts(season.end_ts)? {
+        return Ok(());
+    }
+
+    let season_key = season.key();
+    let stats_info = season_stats
+        .ok_or(PanchoError::SeasonAccountsRequired)?
+        .to_account_info();
+    let (expected_stats, stats_bump) = Pubkey::find_program_address(
+        &[b"season_stats", season_key.as_ref(), user.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(stats_info.key(), expected_stats, PanchoError::SeasonMismatch);
+
+    let mut stats = if *stats_info.owner == crate::ID {
+        SeasonStats::try_deserialize(&mut &stats_info.try_borrow_data()?[..])?
+    } else {
+        let (payer, system_program) = creator.ok_or(PanchoError::SeasonAccountsRequired)?;
+        create_pda_account(
+            payer,
+            &stats_info,
+            system_program,
+            8 + SeasonStats::INIT_SPACE,
+            &[b"season_stats", season_key.as_ref(), user.as_ref(), &[stats_bump]],
+            &crate::ID,
+        )?;
+        SeasonStats {
+            season: season_key,
+            user,
+            pnl_lamports: 0,
+            bump: stats_bump,
+        }
+    };
+    stats.pnl_lamports = stats
+        .pnl_lamports
+        .checked_add(delta)
+        .ok_or(PanchoError::MathOverflow)?;
+    stats.try_serialize(&mut &mut stats_info.try_borrow_mut_data()?[..])?;
+
+    season.table.record(user, stats.pnl_lamports);
+    Ok(())
+}
+
+// Seasons and tournaments share one prize table. Scores move while players trade or play
+// and can be submitted after `end_ts`; the table is final `LEADERBOARD_GRACE_SECONDS` later.
+impl RankedTable {
+    fn new(prize_bps: &[u16]) -> Self {
+        let mut table = Self::default();
+        table.prize_bps[..prize_bps.len()].copy_from_slice(prize_bps);
+        table
+    }
+
+    fn add_to_pool(&mut self, lamports: u64) -> Result<()> {
+        self.prize_pool_lamports = self
+            .prize_pool_lamports
+            .checked_add(lamports)
+            .ok_or(PanchoError::MathOverflow)?;
+        Ok(())
+    }
+
+    // Keeps the table sorted by score. A listed player whose score fell is pushed out
+    // once a higher score is recorded.
+    fn record(&mut self, user: Pubkey, score: i64) {
+        let count = self.entry_count as usize;
+        if let Some(index) = self.entries[..count].iter().position(|entry| entry.user == user) {
+            self.entries[index].score = score;
+        } else if count < LEADERBOARD_SIZE {
+            self.entries[count] = RankEntry { user, score };
+            self.entry_count += 1;
+        } else if score > self.entries[LEADERBOARD_SIZE - 1].score {
+            self.entries[LEADERBOARD_SIZE - 1] = RankEntry { user, score };
+        } else {
+            return;
+        }
+
+        let count = self.entry_count as usize;
+        self.entries[..count].sort_by_key(|entry| std::cmp::Reverse(entry.score));
+    }
+
+    // Marks the user's prize as claimed and returns `(rank, lamports)`.
+    fn take_prize(&mut self, user: Pubkey) -> Result<(usize, u64)> {
+        let rank = self.entries[..self.entry_count as usize]
+            .iter()
+            .position(|entry| entry.user == user)
+            .ok_or(PanchoError::NotPrizeWinner)?;
+        require!(!self.prize_claimed[rank], PanchoError::AlreadyClaimed);
+
+        let lamports = proportion(self.prize_bps[rank] as u64, self.prize_pool_lamports, BPS_DENOMINATOR)?;
+        require!(lamports > 0, PanchoError::NotPrizeWinner);
+        self.prize_claimed[rank] = true;
+        Ok((rank, lamports))
+    }
+}
+
+fn ranking_final_ts(end_ts: i64) -> Result<i64> {
+    end_ts
+        .checked_add(LEADERBOARD_GRACE_SECONDS)
+        .ok_or(error!(PanchoError::MathOverflow))
+}
+
+fn ranking_claim_deadline(config: &GlobalConfig, end_ts: i64) -> Result<i64> {
+    ranking_final_ts(end_ts)?
+        .checked_add(config.claim_window_seconds)
+        .ok_or(error!(PanchoError::MathOverflow))
+}
+
+// The side the round's locked and settled prices favour, ignoring pool participation.
+// Unlocked rounds and unchanged prices have no winner.
+fn price_winner_side(round: &Round) -> u8 {
+    if round.start_price == 0 || round.end_price == 0 || round.start_price == round.end_price {
+        SIDE_NONE
+    } else if round.end_price > round.start_price {
+        SIDE_UP
+    } else {
+        SIDE_DOWN
+    }
+}
+
+// Chips ride on the round's locked and settled prices but are pooled apart from
+// lamports, so a round voided for lack of lamport liquidity still resolves for chips.
+fn chip_payout(round: &Round, position: &ChipPosition) -> Result<u64> {
+    let total = round
+        .up_chips
+        .checked_add(round.down_chips)
+        .ok_or(PanchoError::MathOverflow)?;
+    let winner_side = price_winner_side(round);
+    if winner_side == SIDE_NONE || round.up_chips == 0 || round.down_chips == 0 {
+        return Ok(position.chips);
+    }
+
+    if position.side != winner_side {
+        return Ok(0);
+    }
+    let winner_chips = if winner_side == SIDE_UP {
+        round.up_chips
+    } else {
+        round.down_chips
+    };
+    proportion(position.chips, total, winner_chips)
+}
+
+fn chip_score(chips: u64) -> Result<i64> {
+    i64::try_from(chips).map_err(|_| error!(PanchoError::MathOverflow))
+}
+
+// Sums the daily buckets that fall inside the 30-day window ending today. Buckets are
+// only cleared when the user trades again, so stale ones are skipped by day here.
+fn rolling_volume(user_stats: &UserStats, now: i64) -> u64 {
+    let day = now.div_euclid(SECONDS_PER_DAY);
+    let window_start = day - VOLUME_WINDOW_DAYS as i64;
+    let last_day = user_stats.last_volume_day;
+    (last_day - VOLUME_WINDOW_DAYS as i64 + 1..=last_day)
+        .filter(|bucket_day| *bucket_day > window_start && *bucket_day <= day)
+        .fold(0u64, |total, bucket_day| {
+            total.saturating_add(user_stats.daily_volume_lamports[volume_bucket(bucket_day)])
+        })
+}
+
+fn volume_bucket(day: i64) -> usize {
+    day.rem_euclid(VOLUME_WINDOW_DAYS as i64) as usize
+}
+
+// Routes `referral_fee_bps` of the position's claim fee to the referrer. Shares of
+// users without a referral stay in the vaults with the rest of the fee.
+#[allow(clippy::too_many_arguments)]
+fn pay_referral_share(
+    config: &GlobalConfig,
+    round: &mut Round,
+    position: &Position,
+    fee: u64,
+    referral: &AccountInfo,
+    referrer_rewards: Option<&mut Account<ReferrerRewards>>,
+    up_vault: &AccountInfo,
+    down_vault: &AccountInfo,
+) -> Result<()> {
+    // The referral PDA is always passed, so a claimer cannot skip the share by leaving
+    // it out; an uninitialized PDA means the user has no referrer.
+    let referral = if referral.owner == &crate::ID && !referral.data_is_empty() {
+        Some(Referral::try_deserialize(&mut &referral.try_borrow_data()?[..])?)
+    } else {
+        None
+    };
+    let (referral, referrer_rewards) = match (referral, referrer_rewards) {
+        (None, None) => return Ok(()),
+        (Some(referral), Some(referrer_rewards)) => (referral, referrer_rewards),
+        _ => return err!(PanchoError::ReferralAccountsMismatch),
+    };
+    require_keys_eq!(referral.user, position.user, PanchoError::ReferralAccountsMismatch);
+    require_keys_eq!(
+        referrer_rewards.referrer,
+        referral.referrer,
+        PanchoError::ReferralAccountsMismatch
+    );
+
+    let share = proportion(config.referral_fee_bps as u64, fee, BPS_DENOMINATOR)?;
+    if share == 0 {
+        return Ok(());
+    }
+    round.referral_paid_lamports = round
+        .referral_paid_lamports
+        .checked_add(share)
+        .ok_or(PanchoError::MathOverflow)?;
+
+    transfer_from_vaults(up_vault, down_vault, &referrer_rewards.to_account_info(), share)?;
+    referrer_rewards.accrued_lamports = referrer_rewards
+        .accrued_lamports
+        .checked_add(share)
+        .ok_or(PanchoError::MathOverflow)?;
+    referrer_rewards.total_earned_lamports = referrer_rewards
+        .total_earned_lamports
+        .checked_add(share)
+        .ok_or(PanchoError::MathOverflow)?;
+
+    emit!(ReferralRewardAccrued {
+        round: position.round,
+        user: position.user,
+        referrer: referral.referrer,
+        lamports: share,
+    });
+
+    Ok(())
+}
+
+fn load_round_vault<'info>(
+    vault_info: &'info AccountInfo<'info>,
+    round_key: Pubkey,
+    side: u8,
+    program_id: &Pubkey,
+) -> Result<Account<'info, Vault>> {
+    let vault: Account<Vault> = Account::try_from(vault_info)?;
+    let expected_vault = Pubkey::create_program_address(
+        &[b"vault", round_key.as_ref(), &[side], &[vault.bump]],
+        program_id,
+    )
+    .map_err(|_| error!(PanchoError::VaultRoundMismatch))?;
+    require_keys_eq!(vault_info.key(), expected_vault, PanchoError::VaultRoundMismatch);
+    require_keys_eq!(vault.round, round_key, PanchoError::VaultRoundMismatch);
+    require!(vault.side == side, PanchoError::InvalidSide);
+    Ok(vault)
+}
+
+// Stakes the empty side of a one-sided round from the house vault at lock time so a
+// solo player gets a game instead of a refund. The house matches at most the other
+// side's total, bounded by `seed_cap_lamports` and what the vault can spare.
+// Commit-reveal rounds are skipped: their sides are only known after reveals, which
+// open once the round is already locked.
+fn seed_house_liquidity<'info>(
+    house_vault: &mut Account<'info, HouseVault>,
+    round: &mut Account<'info, Round>,
+    house_position: Option<&UncheckedAccount<'info>>,
+    house_side_vault: Option<&Account<'info, Vault>>,
+    system_program: Option<&Program<'info, System>>,
+    program_id: &Pubkey,
+    now: i64,
+) -> Result<()> {
+    if !house_vault.enabled
+        || round.room != Pubkey::default()
+        || round.commit_reveal
+        || (round.up_total == 0) == (round.down_total == 0)
+    {
+        return Ok(());
+    }
+    let (side, opposing_total) = if round.up_total == 0 {
+        (SIDE_UP, round.down_total)
+    } else {
+        (SIDE_DOWN, round.up_total)
+    };
+
+    let house_info = house_vault.to_account_info();
+    let rent = Rent::get()?;
+    let position_space = 8 + Position::INIT_SPACE;
+    let position_rent = rent.minimum_balance(position_space);
+    let spare = house_info
+        .lamports()
+        .saturating_sub(rent.minimum_balance(house_info.data_len()))
+        .saturating_sub(position_rent);
+    let lamports = opposing_total.min(house_vault.seed_cap_lamports).min(spare);
+    if lamports == 0 {
+        return Ok(());
+    }
+
+    let (house_position, house_side_vault, system_program) =
+        match (house_position, house_side_vault, system_program) {
+            (Some(position), Some(vault), Some(system_program)) => (position, vault, system_program),
+            _ => return err!(PanchoError::HouseAccountsRequired),
+        };
+    require!(house_side_vault.side == side, PanchoError::InvalidSide);
+
+    let round_key = round.key();
+    let house_key = house_vault.key();
+    let (expected_position, position_bump) = Pubkey::find_program_address(
+        &[b"position", round_key.as_ref(), house_key.as_ref(), &[side]],
+        program_id,
+    );
+    require_keys_eq!(
+        house_position.key(),
+        expected_position,
+        PanchoError::InvalidPositionAccount
+    );
+    require_keys_eq!(*house_position.owner, System::id(), PanchoError::InvalidPositionAccount);
+
+    // The house vault carries data, so it cannot fund a system create_account; the
+    // rent is moved across directly and the account is then allocated and assigned.
+    let position_info = house_position.to_account_info();
+    transfer_from_vault(&house_info, &position_info, position_rent)?;
+    create_pda_account(
+        &house_info,
+        &position_info,
+        &system_program.to_account_info(),
+        position_space,
+        &[
+            b"position",
+            round_key.as_ref(),
+            house_key.as_ref(),
+            &[side],
+            &[position_bump],
+        ],
+        program_id,
+    )?;
+
+    let weight = stake_weight(round, lamports, now)?;
+    Position {
+        round: round_key,
+        user: house_key,
+        side,
+        amount: lamports,
+        claimed: false,
+        bump: position_bump,
+        weight,
+        insured_lamports: 0,
+    }
+    .try_serialize(&mut &mut position_info.try_borrow_mut_data()?[..])?;
+    transfer_from_vault(&house_info, &house_side_vault.to_account_info(), lamports)?;
+
+    if side == SIDE_UP {
+        round.up_total = lamports;
+        round.up_weight = weight;
+    } else {
+        round.down_total = lamports;
+        round.down_weight = weight;
+    }
+    // House liquidity is not a player, so it does not count toward
+    // `min_side_participants`; its position still has to be settled by `claim_house`.
+    add_open_account(round)?;
+
+    let market = market_index(round.market)?;
+    house_vault.seeded_lamports[market] = house_vault.seeded_lamports[market]
+        .checked_add(lamports)
+        .ok_or(PanchoError::MathOverflow)?;
+
+    emit!(HouseSeeded {
+        round: round_key,
+        market: round.market,
+        side,
+        lamports,
+    });
+
+    Ok(())
+}
+
+// Reveals open at `lock_ts`, when commits have closed, and only stay open briefly so an
+// unrevealed commitment cannot wait to see most of the round's price move.
+fn reveal_deadline(round: &Round) -> Result<i64> {
+    Ok(round
+        .lock_ts
+        .checked_add(REVEAL_WINDOW_SECONDS)
+        .ok_or(error!(PanchoError::MathOverflow))?
+        .min(round.end_ts))
+}
+
+// Reveals close after the lock price is known, so walking away from a losing side must
+// not be free: refund-mode rounds keep the protocol fee from an unrevealed stake.
+fn unrevealed_penalty(config: &GlobalConfig, round: &Round, lamports: u64) -> Result<u64> {
+    if round.forfeit_unrevealed {
+        Ok(lamports)
+    } else {
+        proportion(config.fee_bps as u64, lamports, BPS_DENOMINATOR)
+    }
+}
+
+fn commitment_hash(side: u8, salt: &[u8; 32], user: Pubkey) -> [u8; 32] {
+    hashv(&[&[side], salt, user.as_ref()]).to_bytes()
+}
+
+struct NewRound {
+    market: u8,
+    round_id: i64,
+    lock_ts: i64,
+    end_ts: i64,
+    feed_id: [u8; 32],
+    oracle_price_account: Pubkey,
+    rent_payer: Pubkey,
+    bump: u8,
+}
+
+fn init_round(config: &GlobalConfig, round: &mut Account<Round>, new_round: NewRound, now: i64) -> Result<()> {
+    let NewRound {
+        market,
+        round_id,
+        lock_ts,
+        end_ts,
+        feed_id,
+        oracle_price_account,
+        rent_payer,
+        bump,
+    } = new_round;
+
+    // PriceUpdateV2 accounts are matched by feed id at read time, so only legacy rounds
+    // pin the oracle account.
+    let oracle_kind = config.oracle_kinds[market_index(market)?];
+    if oracle_kind == ORACLE_KIND_LEGACY {
+        let expected_oracle = expected_oracle_account(config, market)?;
+        require_keys_eq!(
+            oracle_price_account,
+            expected_oracle,
+            PanchoError::UnexpectedOracleAccount
+        );
+    }
+    let expected_feed = expected_feed_id(market)?;
+    require!(feed_id == expected_feed, PanchoError::InvalidFeedId);
+    let entry_cutoff_seconds = entry_cutoff_seconds(config, market)?;
+
+    round.round_id = round_id;
+    round.market = market;
+    round.feed_id = feed_id;
+    round.oracle_price_account = oracle_price_account;
+    round.lock_ts = lock_ts;
+    round.end_ts = end_ts;
+    round.start_price = 0;
+    round.end_price = 0;
+    round.expo = 0;
+    round.status = ROUND_OPEN;
+    round.winner_side = SIDE_NONE;
+    round.up_total = 0;
+    round.down_total = 0;
+    round.fee_lamports = 0;
+    round.distributable_lamports = 0;
+    round.bump = bump;
+    round.tips_paid_lamports = 0;
+    round.settled_ts = 0;
+    round.rent_payer = rent_payer;
+    round.up_count = 0;
+    round.down_count = 0;
+    round.entry_cutoff_ts = lock_ts
+        .checked_sub(entry_cutoff_seconds as i64)
+        .ok_or(PanchoError::MathOverflow)?;
+    round.time_weighted = config.time_weighted_rounds;
+    round.open_ts = now;
+    round.time_weight_floor_bps = config.time_weight_floor_bps;
+    round.up_weight = 0;
+    round.down_weight = 0;
+    round.referral_paid_lamports = 0;
+    round.up_chips = 0;
+    round.down_chips = 0;
+    round.room = Pubkey::default();
+    round.room_host = Pubkey::default();
+    round.host_fee_bps = 0;
+    round.host_fee_lamports = 0;
+    round.commit_reveal = config.commit_reveal_rounds;
+    round.committed_lamports = 0;
+    round.revealed_lamports = 0;
+    round.oracle_kind = oracle_kind;
+    round.open_accounts = 0;
+    round.forfeit_unrevealed = config.forfeit_unrevealed;
+    round.settled_fee_bps = 0;
+    round.settled_fee_lamports = 0;
+    round.claimed_positions = 0;
+
+    emit!(RoundCreated {
+        round: round.key(),
+        round_id,
+        market,
+        lock_ts,
+        end_ts,
+        entry_cutoff_ts: round.entry_cutoff_ts,
+    });
+
+    Ok(())
+}
+
+fn init_round_vault(vault: &mut Vault, round: Pubkey, side: u8, bump: u8) {
+    vault.round = round;
+    vault.side = side;
+    vault.bump = bump;
+}
+
+fn check_room_access(
+    round: &Round,
+    room: Option<&Account<Room>>,
+    user: Pubkey,
+    lamports: u64,
+    proof: &[[u8; 32]],
+) -> Result<()> {
+    if round.room == Pubkey::default() {
+        return Ok(());
+    }
+    let room = room.ok_or(PanchoError::RoomRequired)?;
+    require_keys_eq!(room.key(), round.room, PanchoError::RoomMismatch);
+    require!(
+        room.stake_lamports == 0 || lamports == room.stake_lamports,
+        PanchoError::InvalidStake
+    );
+    require!(
+        verify_allowlist_proof(&room.allowlist_root, user, proof),
+        PanchoError::NotAllowlisted
+    );
+    Ok(())
+}
+
+// Leaves are sha256(wallet) and each pair is hashed in sorted order, so proofs carry
+// no left/right flags.
+fn verify_allowlist_proof(root: &[u8; 32], user: Pubkey, proof: &[[u8; 32]]) -> bool {
+    let leaf = hashv(&[user.as_ref()]).to_bytes();
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        if node <= *sibling {
+            hashv(&[&node, sibling]).to_bytes()
+        } else {
+            hashv(&[sibling, &node]).to_bytes()
+        }
+    });
+    computed == *root
+}
+
+struct JoinStake {
+    user: Pubkey,
+    side: u8,
+    lamports: u64,
+    position_bump: u8,
+    min_multiplier_bps: Option<u64>,
+}
+
+fn apply_join(
+    config: &GlobalConfig,
+    round: &mut Account<Round>,
+    position: &mut Account<Position>,
+    stake: JoinStake,
+    now: i64,
+) -> Result<u64> {
+    require!(stake.lamports > 0, PanchoError::InvalidStake);
+    require!(stake.side == SIDE_UP || stake.side == SIDE_DOWN, PanchoError::InvalidSide);
+    require!(round.status == ROUND_OPEN, PanchoError::RoundNotOpen);
+    require!(now < round.lock_ts, PanchoError::RoundLocked);
+    require!(now < round.entry_cutoff_ts, PanchoError::EntryCutoffPassed);
+    require!(!round.commit_reveal, PanchoError::CommitRevealRound);
+
+    add_stake(config, round, position, stake, now)
+}
+
+// Books a stake onto its position and the round totals. `joined_ts` drives the time
+// weight, so a revealed commitment keeps the weight of its commit time.
+fn add_stake(
+    config: &GlobalConfig,
+    round: &mut Account<Round>,
+    position: &mut Account<Position>,
+    stake: JoinStake,
+    joined_ts: i64,
+) -> Result<u64> {
+    let JoinStake {
+        user,
+        side,
+        lamports,
+        position_bump,
+        min_multiplier_bps,
+    } = stake;
+
+    let is_new_participant = position.amount == 0;
+    if position.round == Pubkey::default() {
+        add_open_account(round)?;
+    }
+    if is_new_participant {
+        position.round = round.key();
+        position.user = user;
+        position.side = side;
+        position.claimed = false;
+        position.bump = position_bump;
+    }
+    require!(position.side == side, PanchoError::PositionSideMismatch);
+    require!(!position.claimed, PanchoError::AlreadyClaimed);
+    position.amount = position
+        .amount
+        .checked_add(lamports)
+        .ok_or(PanchoError::MathOverflow)?;
+    require!(
+        config.max_position_lamports == 0 || position.amount <= config.max_position_lamports,
+        PanchoError::PositionCapExceeded
+    );
+    let weight = stake_weight(round, lamports, joined_ts)?;
+    require!(weight > 0, PanchoError::InvalidStake);
+    position.weight = position
+        .weight
+        .checked_add(weight)
+        .ok_or(PanchoError::MathOverflow)?;
+
+    if side == SIDE_UP {
+        round.up_total = round
+            .up_total
+            .checked_add(lamports)
+            .ok_or(PanchoError::MathOverflow)?;
+    } else {
+        round.down_total = round
+            .down_total
+            .checked_add(lamports)
+            .ok_or(PanchoError::MathOverflow)?;
+    }
+    let round_total = round
+        .up_total
+        .checked_add(round.down_total)
+        .ok_or(PanchoError::MathOverflow)?;
+    require!(
+        config.max_round_lamports == 0 || round_total <= config.max_round_lamports,
+        PanchoError::RoundCapExceeded
+    );
+
+    if is_new_participant {
+        add_participant(round, side)?;
+    }
+    if side == SIDE_UP {
+        round.up_weight = round
+            .up_weight
+            .checked_add(weight)
+            .ok_or(PanchoError::MathOverflow)?;
+    } else {
+        round.down_weight = round
+            .down_weight
+            .checked_add(weight)
+            .ok_or(PanchoError::MathOverflow)?;
+    }
+
+    if let Some(min_multiplier_bps) = min_multiplier_bps {
+        require!(
+            implied_multiplier_bps(round, side, lamports, weight, config.fee_bps)? >= min_multiplier_bps,
+            PanchoError::OddsSlippageExceeded
+        );
+    }
+
+    Ok(weight)
+}
+
+// Multiplier a stake of `lamports` carrying `weight` would get if the round settled now.
+// Winners split by weight, so on time-weighted rounds this is the net pool per unit of
+// side weight scaled by the stake's own weight per lamport.
+fn implied_multiplier_bps(round: &Round, side: u8, lamports: u64, weight: u64, fee_bps: u16) -> Result<u64> {
+    let (side_total, side_weight) = if side == SIDE_UP {
+        (round.up_total, round.up_weight)
+    } else {
+        (round.down_total, round.down_weight)
+    };
+    let (share, side_share) = if round.time_weighted && side_weight > 0 {
+        (weight as u128, side_weight as u128)
+    } else {
+        (lamports as u128, side_total as u128)
+    };
+    if side_share == 0 || lamports == 0 {
+        return Ok(0);
+    }
+
+    let gross = (round.up_total as u128)
+        .checked_add(round.down_total as u128)
+        .ok_or(PanchoError::MathOverflow)?;
+    let fee = gross
+        .checked_mul(fee_bps as u128)
+        .ok_or(PanchoError::MathOverflow)?
+        / BPS_DENOMINATOR as u128;
+    let multiplier = gross
+        .checked_sub(fee)
+        .ok_or(PanchoError::MathOverflow)?
+        .checked_mul(share)
+        .ok_or(PanchoError::MathOverflow)?
+        .checked_mul(BPS_DENOMINATOR as u128)
+        .ok_or(PanchoError::MathOverflow)?
+        / side_share
+        / lamports as u128;
+    u64::try_from(multiplier).map_err(|_| error!(PanchoError::MathOverflow))
+}
+
+// Weight decays linearly from full stake at round creation down to
+// `time_weight_floor_bps` of the stake at `lock_ts`.
+fn stake_weight(round: &Round, lamports: u64, now: i64) -> Result<u64> {
+    if !round.time_weighted {
+        return Ok(lamports);
+    }
+
+    let window = round.lock_ts.saturating_sub(round.open_ts);
+    if window <= 0 {
+        return Ok(lamports);
+    }
+    let remaining = round.lock_ts.saturating_sub(now).clamp(0, window);
+    let floor_bps = round.time_weight_floor_bps as u64;
+    let decaying_bps = proportion(
+        BPS_DENOMINATOR.saturating_sub(floor_bps),
+        remaining as u64,
+        window as u64,
+    )?;
+    proportion(lamports, floor_bps + decaying_bps, BPS_DENOMINATOR)
+}
+
+fn remove_side_weight(round: &mut Round, side: u8, weight: u64) -> Result<()> {
+    if side == SIDE_UP {
+        round.up_weight = round
+            .up_weight
+            .checked_sub(weight)
+            .ok_or(PanchoError::MathOverflow)?;
+    } else {
+        round.down_weight = round
+            .down_weight
+            .checked_sub(weight)
+            .ok_or(PanchoError::MathOverflow)?;
+    }
+    Ok(())
+}
+
+fn add_participant(round: &mut Round, side: u8) -> Result<()> {
+    if side == SIDE_UP {
+        round.up_count = round.up_count.checked_add(1).ok_or(PanchoError::MathOverflow)?;
+    } else {
+        round.down_count = round.down_count.checked_add(1).ok_or(PanchoError::MathOverflow)?;
+    }
+    Ok(())
+}
+
+fn remove_participant(round: &mut Round, side: u8) -> Result<()> {
+    if side == SIDE_UP {
+        round.up_count = round.up_count.checked_sub(1).ok_or(PanchoError::MathOverflow)?;
+    } else {
+        round.down_count = round.down_count.checked_sub(1).ok_or(PanchoError::MathOverflow)?;
+    }
+    Ok(())
+}
+
+// Positions and other PDAs seeded by the round are counted while they exist, so
+// `close_round` cannot free the round's seeds while a stale child could be reused.
+fn add_open_account(round: &mut Round) -> Result<()> {
+    round.open_accounts = round.open_accounts.checked_add(1).ok_or(PanchoError::MathOverflow)?;
+    Ok(())
+}
+
+fn remove_open_account(round: &mut Round) -> Result<()> {
+    round.open_accounts = round.open_accounts.checked_sub(1).ok_or(PanchoError::MathOverflow)?;
+    Ok(())
+}
+
+fn create_pda_account<'info>(
+    payer: &AccountInfo<'info>,
+    new_account: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    seeds: &[&[u8]],
+    owner: &Pubkey,
+) -> Result<()> {
+    let rent_lamports = Rent::get()?.minimum_balance(space);
+    let signer_seeds: &[&[&[u8]]] = &[seeds];
+    let current_lamports = new_account.lamports();
+
+    if current_lamports == 0 {
+        let cpi_accounts = anchor_lang::system_program::CreateAccount {
+            from: payer.clone(),
+            to: new_account.clone(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(system_program.clone(), cpi_accounts, signer_seeds);
+        return anchor_lang::system_program::create_account(
+            cpi_ctx,
+            rent_lamports,
+            space as u64,
+            owner,
+        );
+    }
+
+    let top_up = rent_lamports.saturating_sub(current_lamports);
+    if top_up > 0 {
+        let cpi_accounts = anchor_lang::system_program::Transfer {
+            from: payer.clone(),
+            to: new_account.clone(),
+        };
+        let cpi_ctx = CpiContext::new(system_program.clone(), cpi_accounts);
+        anchor_lang::system_program::transfer(cpi_ctx, top_up)?;
+    }
+
+    let cpi_accounts = anchor_lang::system_program::Allocate {
+        account_to_allocate: new_account.clone(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(system_program.clone(), cpi_accounts, signer_seeds);
+    anchor_lang::system_program::allocate(cpi_ctx, space as u64)?;
+
+    let cpi_accounts = anchor_lang::system_program::Assign {
+        account_to_assign: new_account.clone(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(system_program.clone(), cpi_accounts, signer_seeds);
+    anchor_lang::system_program::assign(cpi_ctx, owner)
+}
+
+fn proportion(numerator: u64, total_out: u64, total_in: u64) -> Result<u64> {
+    if total_in == 0 || total_out == 0 || numerator == 0 {
+        return Ok(0);
+    }
+
+    let result = (numerator as u128)
+        .checked_mul(total_out as u128)
+        .ok_or(error!(PanchoError::MathOverflow))?
+        .checked_div(total_in as u128)
+        .ok_or(error!(PanchoError::MathOverflow))?;
+    u64::try_from(result).map_err(|_| error!(PanchoError::MathOverflow))
+}
+
+fn expected_oracle_account(config: &GlobalConfig, market: u8) -> Result<Pubkey> {
+    match market {
+        0 => Ok(config.oracle_account_sol),
+        1 => Ok(config.oracle_account_btc),
+        2 => Ok(config.oracle_account_eth),
+        _ => Err(error!(PanchoError::InvalidMarket)),
+    }
+}
+
+fn market_index(market: u8) -> Result<usize> {
+    let index = market as usize;
+    require!(index < MARKET_COUNT, PanchoError::InvalidMarket);
+    Ok(index)
+}
+
+fn entry_cutoff_seconds(config: &GlobalConfig, market: u8) -> Result<u32> {
+    match market {
+        0 => Ok(config.entry_cutoff_seconds_sol),
+        1 => Ok(config.entry_cutoff_seconds_btc),
+        2 => Ok(config.entry_cutoff_seconds_eth),
+        _ => Err(error!(PanchoError::InvalidMarket)),
+    }
+}
+
+fn expected_feed_id(market: u8) -> Result<[u8; 32]> {
+    match market {
+        0 => Ok(hex_literal::hex!(
+            "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d"
+        )),
+        1 => Ok(hex_literal::hex!(
+            "e62df6c8b4a85fe1a67db44dc12de5db330f7ac66b72dc658afedf0f4a415b43"
+        )),
+        2 => Ok(hex_literal::hex!(
+            "ff61491a931112ddf1bd8147cd1b641375f79f5825126d665480874634fd0ace"
+        )),
+        _ => Err(error!(PanchoError::InvalidMarket)),
+    }
+}
+
+struct OracleRead {
+    kind: u8,
+    account: Pubkey,
+    feed_id: [u8; 32],
+    min_publish_ts: i64,
+    max_publish_ts: i64,
+}
+
+// Legacy push accounts are pinned by address and aged in slots; they carry no publish
+// time, so callers bound them with their own lock or settle window. PriceUpdateV2
+// accounts are matched by feed id, must be fully verified, must be published inside
+// `[min_publish_ts, max_publish_ts]` and are aged in seconds.
+fn read_oracle_price(config: &GlobalConfig, oracle_price: &UncheckedAccount, read: OracleRead) -> Result<OraclePrice> {
+    let clock = Clock::get()?;
+    match read.kind {
+        ORACLE_KIND_LEGACY => read_legacy_pyth_price(
+            oracle_price,
+            read.account,
+            clock.slot,
+            config.oracle_max_age_sec as u64,
+            config.oracle_program,
+        ),
+        ORACLE_KIND_PRICE_UPDATE_V2 => read_price_update_v2(
+            oracle_price,
+            &read.feed_id,
+            read.min_publish_ts,
+            read.max_publish_ts,
+            clock.unix_timestamp,
+            config.oracle_max_age_seconds as i64,
+        ),
+        _ => Err(error!(PanchoError::InvalidOracleKind)),
+    }
+}
+
+fn duel_oracle_read(config: &GlobalConfig, duel: &Duel, min_publish_ts: i64) -> Result<OracleRead> {
+    Ok(OracleRead {
+        kind: duel.oracle_kind,
+        account: expected_oracle_account(config, duel.market)?,
+        feed_id: expected_feed_id(duel.market)?,
+        min_publish_ts,
+        max_publish_ts: min_publish_ts
+            .checked_add(ORACLE_PUBLISH_TOLERANCE_SECONDS)
+            .ok_or(error!(PanchoError::MathOverflow))?,
+    })
+}
+
+fn settle_read_expired(config: &GlobalConfig, round: &Round, now: i64) -> bool {
+    round.oracle_kind == ORACLE_KIND_PRICE_UPDATE_V2
+        && now
+            > round
+                .end_ts
+                .saturating_add(ORACLE_PUBLISH_TOLERANCE_SECONDS)
+                .saturating_add(config.oracle_max_age_seconds as i64)
+}
+
+// An accepted duel refunds once its lock window passes unlocked, and a locked duel once
+// its settle window passes unsettled.
+fn duel_refundable(duel: &Duel, now: i64) -> bool {
+    match duel.status {
+        DUEL_ACCEPTED => now > duel.lock_ts + LOCK_GRACE_SECONDS,
+        DUEL_LOCKED => now > duel.end_ts + DUEL_SETTLE_GRACE_SECONDS,
+        _ => false,
+    }
+}
+
+fn read_price_update_v2(
+    oracle_price: &UncheckedAccount,
+    expected_feed_id: &[u8; 32],
+    min_publish_ts: i64,
+    max_publish_ts: i64,
+    now: i64,
+    max_age_seconds: i64,
+) -> Result<OraclePrice> {
+    require_keys_eq!(
+        *oracle_price.owner,
+        PYTH_RECEIVER_PROGRAM_ID,
+        PanchoError::InvalidOracleOwner
+    );
+
+    let oracle_info = oracle_price.to_account_info();
+    let data = oracle_info
+        .try_borrow_data()
+        .map_err(|_| error!(PanchoError::InvalidOraclePrice))?;
+    let parsed = parse_price_update_v2_account(&data).ok_or(error!(PanchoError::InvalidOraclePrice))?;
+    require!(parsed.fully_verified, PanchoError::InsufficientOracleVerification);
+    require!(parsed.feed_id == *expected_feed_id, PanchoError::InvalidFeedId);
+    require!(parsed.publish_time >= min_publish_ts, PanchoError::StaleOraclePrice);
+    require!(parsed.publish_time <= max_publish_ts, PanchoError::OraclePriceTooLate);
+    require!(
+        now.saturating_sub(parsed.publish_time) <= max_age_seconds,
+        PanchoError::StaleOraclePrice
+    );
+
+    Ok(OraclePrice {
+        price: parsed.price,
+        expo: parsed.expo,
+    })
+}
+
+fn read_legacy_pyth_price(
+    oracle_price: &UncheckedAccount,
+    expected_oracle_key: Pubkey,
+    now_slot: u64,
+    max_slot_age: u64,
+    expected_oracle_program: Pubkey,
+) -> Result<OraclePrice> {
+    require_keys_eq!(
+        oracle_price.key(),
+        expected_oracle_key,
+        PanchoError::UnexpectedOracleAccount
+    );
+    require_keys_eq!(
+        *oracle_price.owner,
+        expected_oracle_program,
+        PanchoError::InvalidOracleOwner
+    );
+
+    let oracle_info = oracle_price.to_account_info();
+    let data = oracle_info
+        .try_borrow_data()
+        .map_err(|_| error!(PanchoError::InvalidOraclePrice))?;
+    let parsed = parse_legacy_pyth_price_account(&data).ok_or(error!(PanchoError::InvalidOraclePrice))?;
+    require!(parsed.status == LEGACY_PYTH_STATUS_TRADING, PanchoError::InvalidOraclePrice);
+    require!(
+        now_slot.saturating_sub(parsed.pub_slot) <= max_slot_age,
+        PanchoError::StaleOraclePrice
+    );
+
+    Ok(OraclePrice {
+        price: parsed.price,
+        expo: parsed.expo,
+    })
+}
+
+const LEGACY_PYTH_MAGIC: u32 = 0xa1b2c3d4;
+const LEGACY_PYTH_VERSION_2: u32 = 2;
+const LEGACY_PYTH_ACCOUNT_TYPE_PRICE: u32 = 3;
+const LEGACY_PYTH_STATUS_TRADING: u32 = 1;
+const LEGACY_PYTH_OFFSET_MAGIC: usize = 0;
+const LEGACY_PYTH_OFFSET_VERSION: usize = 4;
+const LEGACY_PYTH_OFFSET_ACCOUNT_TYPE: usize = 8;
+const LEGACY_PYTH_OFFSET_EXPO: usize = 20;
+const LEGACY_PYTH_OFFSET_AGG_PRICE: usize = 208;
+const LEGACY_PYTH_OFFSET_AGG_STATUS: usize = 224;
+const LEGACY_PYTH_OFFSET_AGG_PUB_SLOT: usize = 232;
+const LEGACY_PYTH_MIN_LEN: usize = 240;
+
+const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+const PRICE_UPDATE_V2_OFFSET_VERIFICATION: usize = 40;
+const PRICE_UPDATE_V2_VERIFICATION_PARTIAL: u8 = 0;
+const PRICE_UPDATE_V2_VERIFICATION_FULL: u8 = 1;
+const PRICE_MESSAGE_OFFSET_PRICE: usize = 32;
+const PRICE_MESSAGE_OFFSET_EXPO: usize = 48;
+const PRICE_MESSAGE_OFFSET_PUBLISH_TIME: usize = 52;
+
+struct PriceUpdateV2 {
+    feed_id: [u8; 32],
+    price: i64,
+    expo: i32,
+    publish_time: i64,
+    fully_verified: bool,
+}
+
+// `verification_level` is a Borsh enum (`Partial { num_signatures: u8 }` or `Full`), so
+// the price message starts one or two bytes after the write authority.
+fn parse_price_update_v2_account(data: &[u8]) -> Option<PriceUpdateV2> {
+    if data.get(..8)? != PRICE_UPDATE_V2_DISCRIMINATOR {
+        return None;
+    }
+
+    let (fully_verified, message) = match *data.get(PRICE_UPDATE_V2_OFFSET_VERIFICATION)? {
+        PRICE_UPDATE_V2_VERIFICATION_PARTIAL => (false, PRICE_UPDATE_V2_OFFSET_VERIFICATION + 2),
+        PRICE_UPDATE_V2_VERIFICATION_FULL => (true, PRICE_UPDATE_V2_OFFSET_VERIFICATION + 1),
+        _ => return None,
+    };
+
+    Some(PriceUpdateV2 {
+        feed_id: data.get(message..message + 32)?.try_into().ok()?,
+        price: read_i64_le(data, message + PRICE_MESSAGE_OFFSET_PRICE)?,
+        expo: read_i32_le(data, message + PRICE_MESSAGE_OFFSET_EXPO)?,
+        publish_time: read_i64_le(data, message + PRICE_MESSAGE_OFFSET_PUBLISH_TIME)?,
+        fully_verified,
+    })
+}
+
+struct LegacyPythPrice {
+    price: i64,
+    expo: i32,
+    status: u32,
+    pub_slot: u64,
+}
+
+fn read_u32_le(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset + 4)?;
+    Some(u32::from_le_bytes(bytes.try_into().ok()?))
+}
+
+fn read_i32_le(data: &[u8], offset: usize) -> Option<i32> {
+    let bytes = data.get(offset..offset + 4)?;
+    Some(i32::from_le_bytes(bytes.try_into().ok()?))
+}
+
+fn read_i64_le(data: &[u8], offset: usize) -> Option<i64> {
+    let bytes = data.get(offset..offset + 8)?;
+    Some(i64::from_le_bytes(bytes.try_into().ok()?))
+}
+
+fn read_u64_le(data: &[u8], offset: usize) -> Option<u64> {
+    let bytes = data.get(offset..offset + 8)?;
+    Some(u64::from_le_bytes(bytes.try_into().ok()?))
+}
+
+fn parse_legacy_pyth_price_account(data: &[u8]) -> Option<LegacyPythPrice> {
+    if data.len() < LEGACY_PYTH_MIN_LEN {
+        return None;
+    }
+
+    if read_u32_le(data, LEGACY_PYTH_OFFSET_MAGIC)? != LEGACY_PYTH_MAGIC {
+        return None;
+    }
+    if read_u32_le(data, LEGACY_PYTH_OFFSET_VERSION)? != LEGACY_PYTH_VERSION_2 {
+        return None;
+    }
+    if read_u32_le(data, LEGACY_PYTH_OFFSET_ACCOUNT_TYPE)? != LEGACY_PYTH_ACCOUNT_TYPE_PRICE {
+        return None;
+    }
+
+    Some(LegacyPythPrice {
+        price: read_i64_le(data, LEGACY_PYTH_OFFSET_AGG_PRICE)?,
+        expo: read_i32_le(data, LEGACY_PYTH_OFFSET_EXPO)?,
+        status: read_u32_le(data, LEGACY_PYTH_OFFSET_AGG_STATUS)?,
+        pub_slot: read_u64_le(data, LEGACY_PYTH_OFFSET_AGG_PUB_SLOT)?,
+    })
+}
+
+fn transfer_from_vaults(up_vault: &AccountInfo, down_vault: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    debit_vaults(up_vault, down_vault, amount)?;
+    credit_lamports(to, amount)
+}
+
+// Splits `amount` across the configured fee recipients, which are passed in config
+// order through `remaining_accounts`. Rounding dust, or the whole amount when no split
+// table is configured, goes to the treasury.
+fn distribute_protocol_fee(
+    config: &GlobalConfig,
+    round: Pubkey,
+    up_vault: &AccountInfo,
+    down_vault: &AccountInfo,
+    treasury: &AccountInfo,
+    recipients: &[AccountInfo],
+    amount: u64,
+) -> Result<()> {
+    let count = config.fee_split_count as usize;
+    require!(recipients.len() == count, PanchoError::FeeRecipientMismatch);
+
+    let rent = Rent::get()?;
+    let mut distributed: u64 = 0;
+    for (index, recipient) in recipients.iter().enumerate() {
+        require_keys_eq!(
+            recipient.key(),
+            config.fee_split_recipients[index],
+            PanchoError::FeeRecipientMismatch
+        );
+        let share = proportion(amount, config.fee_split_bps[index] as u64, BPS_DENOMINATOR)?;
+        // A recipient drained below rent exemption would fail the whole settlement, so
+        // its share falls through to the treasury instead.
+        let balance = recipient
+            .lamports()
+            .checked_add(share)
+            .ok_or(PanchoError::MathOverflow)?;
+        if share == 0 || !rent.is_exempt(balance, recipient.data_len()) {
+            continue;
+        }
+        transfer_from_vaults(up_vault, down_vault, recipient, share)?;
+        distributed = distributed
+            .checked_add(share)
+            .ok_or(PanchoError::MathOverflow)?;
+
+        emit!(FeeDistributed {
+            round,
+            recipient: recipient.key(),
+            lamports: share,
+        });
+    }
+
+    let remainder = amount
+        .checked_sub(distributed)
+        .ok_or(PanchoError::MathOverflow)?;
+    transfer_from_vaults(up_vault, down_vault, treasury, remainder)
+}
+
+fn reserved_fee_bps(config: &GlobalConfig) -> u64 {
+    config.crank_tip_bps as u64 + config.referral_fee_bps as u64 + config.insurance_fee_bps as u64
+}
+
+fn deposit_to_insurance(
+    insurance_fund: &mut Account<InsuranceFund>,
+    up_vault: &AccountInfo,
+    down_vault: &AccountInfo,
+    round: Pubkey,
+    lamports: u64,
+) -> Result<()> {
+    if lamports == 0 {
+        return Ok(());
+    }
+
+    transfer_from_vaults(up_vault, down_vault, &insurance_fund.to_account_info(), lamports)?;
+    insurance_fund.total_deposited_lamports = insurance_fund
+        .total_deposited_lamports
+        .checked_add(lamports)
+        .ok_or(PanchoError::MathOverflow)?;
+
+    emit!(InsuranceDeposited { round, lamports });
+
+    Ok(())
+}
+
+fn transfer_from_vault(vault: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let available = vault.lamports();
+    require!(available >= amount, PanchoError::InsufficientVaultLiquidity);
+    **vault.try_borrow_mut_lamports()? = available
+        .checked_sub(amount)
+        .ok_or(error!(PanchoError::MathOverflow))?;
+    credit_lamports(to, amount)
+}
+
+fn debit_vaults(up_vault: &AccountInfo, down_vault: &AccountInfo, amount: u64) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let mut remaining = amount;
+    let up_available = up_vault.lamports();
+    let take_up = up_available.min(remaining);
+    if take_up > 0 {
+        **up_vault.try_borrow_mut_lamports()? = up_available
+            .checked_sub(take_up)
+            .ok_or(error!(PanchoError::MathOverflow))?;
+        remaining = remaining
+            .checked_sub(take_up)
+            .ok_or(error!(PanchoError::MathOverflow))?;
+    }
+
+    if remaining > 0 {
+        let down_available = down_vault.lamports();
+        let take_down = down_available.min(remaining);
+        if take_down > 0 {
+            **down_vault.try_borrow_mut_lamports()? = down_available
+                .checked_sub(take_down)
+                .ok_or(error!(PanchoError::MathOverflow))?;
+            remaining = remaining
+                .checked_sub(take_down)
+                .ok_or(error!(PanchoError::MathOverflow))?;
+        }
+    }
+
+    require!(remaining == 0, PanchoError::InsufficientVaultLiquidity);
+    Ok(())
+}
+
+fn credit_lamports(to: &AccountInfo, amount: u64) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    **to.try_borrow_mut_lamports()? = to
+        .lamports()
+        .checked_add(amount)
+        .ok_or(error!(PanchoError::MathOverflow))?;
+    Ok(())
+}
+
+struct OraclePrice {
+    price: i64,
+    expo: i32,
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(mut, address = INITIAL_ADMIN)]
+    pub admin: Signer<'info>,
+    /// CHECK: destination treasury wallet
+    pub treasury: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + GlobalConfig::INIT_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SetTreasury<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    /// CHECK: destination treasury wallet
+    pub new_treasury: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetOracleKind<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    /// CHECK: written by an older build, so it may be shorter than `GlobalConfig`; the
+    /// discriminator and admin are checked in the handler before it is resized
+    #[account(mut, seeds = [b"config"], bump, owner = crate::ID)]
+    pub config: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetOracleMaxAgeSeconds<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SetOracleAccounts<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SetCrankTip<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SetClaimWindow<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SetExitFee<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SetSwitchFee<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SetStakeLimits<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SetParticipationRules<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SetEntryCutoffs<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SetTimeWeighting<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SetCommitReveal<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SetReferralFee<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SetInsuranceConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeInsuranceFund<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + InsuranceFund::INIT_SPACE,
+        seeds = [b"insurance"],
+        bump
+    )]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InsurancePayout<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(mut, seeds = [b"insurance"], bump = insurance_fund.bump)]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+    #[account(mut)]
+    pub round: Account<'info, Round>,
+    #[account(
+        mut,
+        seeds = [b"position", round.key().as_ref(), position.user.as_ref(), &[position.side]],
+        bump = position.bump
+    )]
+    pub position: Option<Account<'info, Position>>,
+    /// CHECK: validated in handler against the payout target
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeHouseVault<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + HouseVault::INIT_SPACE,
+        seeds = [b"house"],
+        bump
+    )]
+    pub house_vault: Account<'info, HouseVault>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetHouseConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(mut, seeds = [b"house"], bump = house_vault.bump)]
+    pub house_vault: Account<'info, HouseVault>,
+}
+
+#[derive(Accounts)]
+pub struct FundHouseVault<'info> {
+    #[account(mut, address = config.treasury)]
+    pub treasury: Signer<'info>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(mut, seeds = [b"house"], bump = house_vault.bump)]
+    pub house_vault: Account<'info, HouseVault>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawHouseVault<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(mut, seeds = [b"house"], bump = house_vault.bump)]
+    pub house_vault: Account<'info, HouseVault>,
+    /// CHECK: validated against config.treasury
+    #[account(mut, address = config.treasury)]
+    pub treasury: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetFeeSplit<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+#[instruction(season_id: u32)]
+pub struct CreateSeason<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Season::INIT_SPACE,
+        seeds = [b"season", season_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub season: Account<'info, Season>,
+    pub system_program: Program<'info, System>,
+    /// The currently active season, required while one is set.
+    pub current_season: Option<Account<'info, Season>>,
+}
+
+#[derive(Accounts)]
+pub struct SetActiveSeason<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(seeds = [b"season", season.season_id.to_le_bytes().as_ref()], bump = season.bump)]
+    pub season: Option<Account<'info, Season>>,
+}
+
+#[derive(Accounts)]
+pub struct FundSeason<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(
+        mut,
+        seeds = [b"season", season.season_id.to_le_bytes().as_ref()],
+        bump = season.bump
+    )]
+    pub season: Account<'info, Season>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimSeasonPrize<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"season", season.season_id.to_le_bytes().as_ref()],
+        bump = season.bump
+    )]
+    pub season: Account<'info, Season>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitSeasonScore<'info> {
+    #[account(
+        mut,
+        seeds = [b"season", season.season_id.to_le_bytes().as_ref()],
+        bump = season.bump
+    )]
+    pub season: Account<'info, Season>,
+    #[account(
+        seeds = [b"season_stats", season.key().as_ref(), season_stats.user.as_ref()],
+        bump = season_stats.bump
+    )]
+    pub season_stats: Account<'info, SeasonStats>,
+}
+
+#[derive(Accounts)]
+pub struct CloseSeason<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(
+        mut,
+        close = treasury,
+        seeds = [b"season", season.season_id.to_le_bytes().as_ref()],
+        bump = season.bump
+    )]
+    pub season: Account<'info, Season>,
+    /// CHECK: validated against config.treasury
+    #[account(mut, address = config.treasury)]
+    pub treasury: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(tournament_id: u32)]
+pub struct CreateTournament<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Tournament::INIT_SPACE,
+        seeds = [b"tournament", tournament_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct EnterTournament<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+    #[account(
+        init,
+        payer = user,
+        space = 8 + Entrant::INIT_SPACE,
+        seeds = [b"entrant", tournament.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub entrant: Account<'info, Entrant>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(side: u8)]
+pub struct JoinRoundChips<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+    #[account(
+        mut,
+        seeds = [b"entrant", tournament.key().as_ref(), user.key().as_ref()],
+        bump = entrant.bump
+    )]
+    pub entrant: Account<'info, Entrant>,
+    #[account(mut)]
+    pub round: Account<'info, Round>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + ChipPosition::INIT_SPACE,
+        seeds = [b"chip_position", round.key().as_ref(), entrant.key().as_ref(), &[side]],
+        bump
+    )]
+    pub chip_position: Account<'info, ChipPosition>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimChips<'info> {
+    pub claimer: Signer<'info>,
+    /// CHECK: the entrant's owner; receives the chip position rent
+    #[account(mut, address = entrant.user)]
+    pub user: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+    #[account(
+        mut,
+        seeds = [b"entrant", tournament.key().as_ref(), entrant.user.as_ref()],
+        bump = entrant.bump
+    )]
+    pub entrant: Account<'info, Entrant>,
+    #[account(mut)]
+    pub round: Account<'info, Round>,
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"chip_position", round.key().as_ref(), entrant.key().as_ref(), &[chip_position.side]],
+        bump = chip_position.bump,
+        constraint = chip_position.round == round.key() @ PanchoError::PositionRoundMismatch
+    )]
+    pub chip_position: Account<'info, ChipPosition>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimTournamentPrize<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitScore<'info> {
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+    #[account(
+        seeds = [b"entrant", tournament.key().as_ref(), entrant.user.as_ref()],
+        bump = entrant.bump
+    )]
+    pub entrant: Account<'info, Entrant>,
+}
+
+#[derive(Accounts)]
+pub struct CloseEntrant<'info> {
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"entrant", tournament.key().as_ref(), entrant.user.as_ref()],
+        bump = entrant.bump
+    )]
+    pub entrant: Account<'info, Entrant>,
+    /// CHECK: the entrant's owner; receives the rent
+    #[account(mut, address = entrant.user)]
+    pub user: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseTournament<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(
+        mut,
+        close = treasury,
+        seeds = [b"tournament", tournament.tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+    /// CHECK: validated against config.treasury
+    #[account(mut, address = config.treasury)]
+    pub treasury: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetFeeTiers<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+#[instruction(market: u8, round_id: i64)]
+pub struct CreateRound<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Round::INIT_SPACE,
+        seeds = [b"round".as_ref(), &[market], &round_id.to_le_bytes()],
+        bump
+    )]
+    pub round: Account<'info, Round>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Vault::INIT_SPACE,
+        seeds = [b"vault", round.key().as_ref(), &[SIDE_UP]],
+        bump
+    )]
+    pub up_vault: Account<'info, Vault>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Vault::INIT_SPACE,
+        seeds = [b"vault", round.key().as_ref(), &[SIDE_DOWN]],
+        bump
+    )]
+    pub down_vault: Account<'info, Vault>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(room_id: u32)]
+pub struct CreateRoom<'info> {
+    #[account(mut)]
+    pub host: Signer<'info>,
+    #[account(
+        init,
+        payer = host,
+        space = 8 + Room::INIT_SPACE,
+        seeds = [b"room", host.key().as_ref(), room_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub room: Account<'info, Room>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(market: u8, round_id: i64)]
+pub struct CreateRoomRound<'info> {
+    #[account(mut)]
+    pub host: Signer<'info>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(
+        mut,
+        seeds = [b"room", host.key().as_ref(), room.room_id.to_le_bytes().as_ref()],
+        bump = room.bump,
+        has_one = host
+    )]
+    pub room: Account<'info, Room>,
+    #[account(
+        init,
+        payer = host,
+        space = 8 + Round::INIT_SPACE,
+        seeds = [b"room_round", room.key().as_ref(), &round_id.to_le_bytes()],
+        bump
+    )]
+    pub round: Account<'info, Round>,
+    #[account(
+        init,
+        payer = host,
+        space = 8 + Vault::INIT_SPACE,
+        seeds = [b"vault", round.key().as_ref(), &[SIDE_UP]],
+        bump
+    )]
+    pub up_vault: Account<'info, Vault>,
+    #[account(
+        init,
+        payer = host,
+        space = 8 + Vault::INIT_SPACE,
+        seeds = [b"vault", round.key().as_ref(), &[SIDE_DOWN]],
+        bump
+    )]
+    pub down_vault: Account<'info, Vault>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(side: u8)]
+pub struct JoinRound<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(mut)]
+    pub round: Account<'info, Round>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + Position::INIT_SPACE,
+        seeds = [b"position", round.key().as_ref(), user.key().as_ref(), &[side]],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+    #[account(
+        mut,
+        seeds = [b"vault", round.key().as_ref(), &[side]],
+        bump = side_vault.bump,
+        constraint = side_vault.round == round.key() @ PanchoError::VaultRoundMismatch,
+        constraint = side_vault.side == side @ PanchoError::InvalidSide
+    )]
+    pub side_vault: Account<'info, Vault>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserStats::INIT_SPACE,
+        seeds = [b"user_stats", user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+    #[account(mut)]
+    pub season: Option<Account<'info, Season>>,
+    /// CHECK: `[b"season_stats", season, user]` PDA, validated and created in handler
+    #[account(mut)]
+    pub season_stats: Option<UncheckedAccount<'info>>,
+    pub room: Option<Account<'info, Room>>,
+}
+
+#[derive(Accounts)]
+pub struct JoinMany<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserStats::INIT_SPACE,
+        seeds = [b"user_stats", user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+    #[account(mut)]
+    pub season: Option<Account<'info, Season>>,
+    /// CHECK: `[b"season_stats", season, user]` PDA, validated and created in handler
+    #[account(mut)]
+    pub season_stats: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct CommitStake<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(mut)]
+    pub round: Account<'info, Round>,
+    #[account(
+        init,
+        payer = user,
+        space = 8 + Commitment::INIT_SPACE,
+        seeds = [b"commitment", round.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub commitment: Account<'info, Commitment>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(side: u8)]
+pub struct RevealStake<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(mut)]
+    pub round: Account<'info, Round>,
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"commitment", round.key().as_ref(), user.key().as_ref()],
+        bump = commitment.bump
+    )]
+    pub commitment: Account<'info, Commitment>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + Position::INIT_SPACE,
+        seeds = [b"position", round.key().as_ref(), user.key().as_ref(), &[side]],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+    #[account(
+        mut,
+        seeds = [b"vault", round.key().as_ref(), &[side]],
+        bump = side_vault.bump,
+        constraint = side_vault.round == round.key() @ PanchoError::VaultRoundMismatch,
+        constraint = side_vault.side == side @ PanchoError::InvalidSide
+    )]
+    pub side_vault: Account<'info, Vault>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserStats::INIT_SPACE,
+        seeds = [b"user_stats", user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+    #[account(mut)]
+    pub season: Option<Account<'info, Season>>,
+    /// CHECK: `[b"season_stats", season, user]` PDA, validated and created in handler
+    #[account(mut)]
+    pub season_stats: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct ForfeitCommitment<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(mut, address = commitment.round @ PanchoError::PositionRoundMismatch)]
+    pub round: Account<'info, Round>,
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"commitment", round.key().as_ref(), user.key().as_ref()],
+        bump = commitment.bump,
+        has_one = user
+    )]
+    pub commitment: Account<'info, Commitment>,
+    /// CHECK: validated against commitment.user
+    #[account(mut)]
+    pub user: UncheckedAccount<'info>,
+    /// CHECK: validated against config.treasury
+    #[account(mut, address = config.treasury)]
+    pub treasury: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawPosition<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(mut)]
+    pub round: Account<'info, Round>,
+    #[account(
+        mut,
+        seeds = [b"position", round.key().as_ref(), user.key().as_ref(), &[position.side]],
+        bump = position.bump,
+        constraint = position.round == round.key() @ PanchoError::PositionRoundMismatch,
+        constraint = position.user == user.key() @ PanchoError::PositionUserMismatch
+    )]
+    pub position: Account<'info, Position>,
+    #[account(
+        mut,
+        seeds = [b"vault", round.key().as_ref(), &[position.side]],
+        bump = side_vault.bump,
+        constraint = side_vault.round == round.key() @ PanchoError::VaultRoundMismatch
+    )]
+    pub side_vault: Account<'info, Vault>,
+    /// CHECK: validated against config.treasury
+    #[account(mut, address = config.treasury)]
+    pub treasury: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"user_stats", user.key().as_ref()], bump = user_stats.bump)]
+    pub user_stats: Account<'info, UserStats>,
+    #[account(mut)]
+    pub season: Option<Account<'info, Season>>,
+    /// CHECK: `[b"season_stats", season, user]` PDA, validated in handler
+    #[account(mut)]
+    pub season_stats: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+#[instruction(to_side: u8)]
+pub struct SwitchSide<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(mut)]
+    pub round: Account<'info, Round>,
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"position", round.key().as_ref(), user.key().as_ref(), &[to_side ^ 1]],
+        bump = from_position.bump,
+        constraint = from_position.round == round.key() @ PanchoError::PositionRoundMismatch,
+        constraint = from_position.user == user.key() @ PanchoError::PositionUserMismatch
+    )]
+    pub from_position: Account<'info, Position>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + Position::INIT_SPACE,
+        seeds = [b"position", round.key().as_ref(), user.key().as_ref(), &[to_side]],
+        bump
+    )]
+    pub to_position: Account<'info, Position>,
+    #[account(
+        mut,
+        seeds = [b"vault", round.key().as_ref(), &[to_side ^ 1]],
+        bump = from_vault.bump,
+        constraint = from_vault.round == round.key() @ PanchoError::VaultRoundMismatch
+    )]
+    pub from_vault: Account<'info, Vault>,
+    #[account(
+        mut,
+        seeds = [b"vault", round.key().as_ref(), &[to_side]],
+        bump = to_vault.bump,
+        constraint = to_vault.round == round.key() @ PanchoError::VaultRoundMismatch
+    )]
+    pub to_vault: Account<'info, Vault>,
+    /// CHECK: validated against config.treasury
+    #[account(mut, address = config.treasury)]
+    pub treasury: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    #[account(mut, seeds = [b"user_stats", user.key().as_ref()], bump = user_stats.bump)]
+    pub user_stats: Account<'info, UserStats>,
+}
+
+#[derive(Accounts)]
+pub struct LockRound<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(mut)]
+    pub round: Account<'info, Round>,
+    /// CHECK: validated in handler
+    pub oracle_price: UncheckedAccount<'info>,
+    // Required so a caller cannot skip seeding; the other house accounts are only
+    // needed when the vault is enabled and the round is one-sided.
+    #[account(mut, seeds = [b"house"], bump = house_vault.bump)]
+    pub house_vault: Account<'info, HouseVault>,
+    /// CHECK: house position PDA for the empty side, created in handler
+    #[account(mut)]
+    pub house_position: Option<UncheckedAccount<'info>>,
+    #[account(
+        mut,
+        seeds = [b"vault", round.key().as_ref(), &[house_side_vault.side]],
+        bump = house_side_vault.bump,
+        constraint = house_side_vault.round == round.key() @ PanchoError::VaultRoundMismatch
+    )]
+    pub house_side_vault: Option<Account<'info, Vault>>,
+    pub system_program: Option<Program<'info, System>>,
+}
+
+#[derive(Accounts)]
+pub struct SettleRound<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(mut)]
+    pub round: Account<'info, Round>,
     #[account(
-        init,
-        payer = admin,
-        space = 8 + GlobalConfig::INIT_SPACE,
-        seeds = [b"config"],
-        bump
+        mut,
+        seeds = [b"vault", round.key().as_ref(), &[SIDE_UP]],
+        bump = up_vault.bump,
+        constraint = up_vault.round == round.key() @ PanchoError::VaultRoundMismatch
     )]
-    pub config: Account<'info, GlobalConfig>,
-    pub system_program: Program<'info, System>,
+    pub up_vault: Account<'info, Vault>,
+    #[account(
+        mut,
+        seeds = [b"vault", round.key().as_ref(), &[SIDE_DOWN]],
+        bump = down_vault.bump,
+        constraint = down_vault.round == round.key() @ PanchoError::VaultRoundMismatch
+    )]
+    pub down_vault: Account<'info, Vault>,
+    /// CHECK: validated in handler
+    pub oracle_price: UncheckedAccount<'info>,
+    /// CHECK: validated against config.treasury
+    #[account(mut, address = config.treasury)]
+    pub treasury: UncheckedAccount<'info>,
+    /// CHECK: validated against round.room_host in handler
+    #[account(mut)]
+    pub room_host: Option<UncheckedAccount<'info>>,
+    #[account(mut, seeds = [b"insurance"], bump = insurance_fund.bump)]
+    pub insurance_fund: Option<Account<'info, InsuranceFund>>,
 }
 
 #[derive(Accounts)]
-pub struct SetConfig<'info> {
+pub struct Claim<'info> {
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub user: Signer<'info>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(mut)]
+    pub round: Account<'info, Round>,
     #[account(
         mut,
-        seeds = [b"config"],
-        bump = config.bump,
-        has_one = admin
+        seeds = [b"position", round.key().as_ref(), user.key().as_ref(), &[position.side]],
+        bump = position.bump,
+        constraint = position.round == round.key() @ PanchoError::PositionRoundMismatch,
+        constraint = position.user == user.key() @ PanchoError::PositionUserMismatch
     )]
-    pub config: Account<'info, GlobalConfig>,
+    pub position: Account<'info, Position>,
+    #[account(
+        mut,
+        seeds = [b"vault", round.key().as_ref(), &[SIDE_UP]],
+        bump = up_vault.bump,
+        constraint = up_vault.round == round.key() @ PanchoError::VaultRoundMismatch
+    )]
+    pub up_vault: Account<'info, Vault>,
+    #[account(
+        mut,
+        seeds = [b"vault", round.key().as_ref(), &[SIDE_DOWN]],
+        bump = down_vault.bump,
+        constraint = down_vault.round == round.key() @ PanchoError::VaultRoundMismatch
+    )]
+    pub down_vault: Account<'info, Vault>,
+    /// CHECK: `[b"referral", user]` PDA, deserialized in handler when initialized
+    #[account(seeds = [b"referral", user.key().as_ref()], bump)]
+    pub referral: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"referrer_rewards", referrer_rewards.referrer.as_ref()],
+        bump = referrer_rewards.bump
+    )]
+    pub referrer_rewards: Option<Account<'info, ReferrerRewards>>,
+    #[account(mut, seeds = [b"user_stats", user.key().as_ref()], bump = user_stats.bump)]
+    pub user_stats: Account<'info, UserStats>,
+    #[account(mut)]
+    pub season: Option<Account<'info, Season>>,
+    /// CHECK: `[b"season_stats", season, user]` PDA, validated and created in handler
+    #[account(mut)]
+    pub season_stats: Option<UncheckedAccount<'info>>,
 }
 
 #[derive(Accounts)]
-pub struct SetTreasury<'info> {
+pub struct ClaimFor<'info> {
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub cranker: Signer<'info>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(mut)]
+    pub round: Account<'info, Round>,
     #[account(
         mut,
-        seeds = [b"config"],
-        bump = config.bump,
-        has_one = admin
+        seeds = [b"position", round.key().as_ref(), position.user.as_ref(), &[position.side]],
+        bump = position.bump,
+        constraint = position.round == round.key() @ PanchoError::PositionRoundMismatch
     )]
-    pub config: Account<'info, GlobalConfig>,
-    /// CHECK: destination treasury wallet
-    pub new_treasury: UncheckedAccount<'info>,
+    pub position: Account<'info, Position>,
+    /// CHECK: payout destination, validated against position.user
+    #[account(
+        mut,
+        address = position.user @ PanchoError::PositionUserMismatch,
+        constraint = *user.owner != crate::ID @ PanchoError::HouseClaimRequired
+    )]
+    pub user: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault", round.key().as_ref(), &[SIDE_UP]],
+        bump = up_vault.bump,
+        constraint = up_vault.round == round.key() @ PanchoError::VaultRoundMismatch
+    )]
+    pub up_vault: Account<'info, Vault>,
+    #[account(
+        mut,
+        seeds = [b"vault", round.key().as_ref(), &[SIDE_DOWN]],
+        bump = down_vault.bump,
+        constraint = down_vault.round == round.key() @ PanchoError::VaultRoundMismatch
+    )]
+    pub down_vault: Account<'info, Vault>,
+    /// CHECK: `[b"referral", position.user]` PDA, deserialized in handler when initialized
+    #[account(seeds = [b"referral", position.user.as_ref()], bump)]
+    pub referral: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"referrer_rewards", referrer_rewards.referrer.as_ref()],
+        bump = referrer_rewards.bump
+    )]
+    pub referrer_rewards: Option<Account<'info, ReferrerRewards>>,
+    #[account(mut, seeds = [b"user_stats", position.user.as_ref()], bump = user_stats.bump)]
+    pub user_stats: Account<'info, UserStats>,
+    #[account(mut)]
+    pub season: Option<Account<'info, Season>>,
+    /// CHECK: `[b"season_stats", season, user]` PDA, validated and created in handler
+    #[account(mut)]
+    pub season_stats: Option<UncheckedAccount<'info>>,
 }
 
 #[derive(Accounts)]
-pub struct SetOracleAccounts<'info> {
+pub struct ClaimHouse<'info> {
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub round: Account<'info, Round>,
+    #[account(mut, seeds = [b"house"], bump = house_vault.bump)]
+    pub house_vault: Account<'info, HouseVault>,
     #[account(
         mut,
-        seeds = [b"config"],
-        bump = config.bump,
-        has_one = admin
+        close = house_vault,
+        seeds = [b"position", round.key().as_ref(), house_vault.key().as_ref(), &[position.side]],
+        bump = position.bump,
+        constraint = position.round == round.key() @ PanchoError::PositionRoundMismatch
     )]
-    pub config: Account<'info, GlobalConfig>,
+    pub position: Account<'info, Position>,
+    #[account(
+        mut,
+        seeds = [b"vault", round.key().as_ref(), &[SIDE_UP]],
+        bump = up_vault.bump,
+        constraint = up_vault.round == round.key() @ PanchoError::VaultRoundMismatch
+    )]
+    pub up_vault: Account<'info, Vault>,
+    #[account(
+        mut,
+        seeds = [b"vault", round.key().as_ref(), &[SIDE_DOWN]],
+        bump = down_vault.bump,
+        constraint = down_vault.round == round.key() @ PanchoError::VaultRoundMismatch
+    )]
+    pub down_vault: Account<'info, Vault>,
 }
 
 #[derive(Accounts)]
-#[instruction(market: u8, round_id: i64)]
-pub struct CreateRound<'info> {
+pub struct ClosePosition<'info> {
+    pub closer: Signer<'info>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub round: Account<'info, Round>,
     #[account(
-        seeds = [b"config"],
-        bump = config.bump,
-        has_one = admin
+        mut,
+        close = user,
+        seeds = [b"position", round.key().as_ref(), position.user.as_ref(), &[position.side]],
+        bump = position.bump,
+        constraint = position.round == round.key() @ PanchoError::PositionRoundMismatch
     )]
-    pub config: Account<'info, GlobalConfig>,
+    pub position: Account<'info, Position>,
+    /// CHECK: rent destination, validated against position.user
     #[account(
-        init,
-        payer = admin,
-        space = 8 + Round::INIT_SPACE,
-        seeds = [b"round".as_ref(), &[market], &round_id.to_le_bytes()],
-        bump
+        mut,
+        address = position.user @ PanchoError::PositionUserMismatch,
+        constraint = *user.owner != crate::ID @ PanchoError::HouseClaimRequired
     )]
+    pub user: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"user_stats", position.user.as_ref()], bump = user_stats.bump)]
+    pub user_stats: Account<'info, UserStats>,
+}
+
+#[derive(Accounts)]
+pub struct GetUserStats<'info> {
+    #[account(seeds = [b"user_stats", user_stats.user.as_ref()], bump = user_stats.bump)]
+    pub user_stats: Account<'info, UserStats>,
+}
+
+#[derive(Accounts)]
+pub struct CloseRound<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(mut, close = rent_payer)]
     pub round: Account<'info, Round>,
     #[account(
-        init,
-        payer = admin,
-        space = 8 + Vault::INIT_SPACE,
+        mut,
+        close = rent_payer,
         seeds = [b"vault", round.key().as_ref(), &[SIDE_UP]],
-        bump
+        bump = up_vault.bump,
+        constraint = up_vault.round == round.key() @ PanchoError::VaultRoundMismatch
     )]
     pub up_vault: Account<'info, Vault>,
     #[account(
-        init,
-        payer = admin,
-        space = 8 + Vault::INIT_SPACE,
+        mut,
+        close = rent_payer,
         seeds = [b"vault", round.key().as_ref(), &[SIDE_DOWN]],
-        bump
+        bump = down_vault.bump,
+        constraint = down_vault.round == round.key() @ PanchoError::VaultRoundMismatch
     )]
     pub down_vault: Account<'info, Vault>,
-    pub system_program: Program<'info, System>,
+    /// CHECK: validated against config.treasury
+    #[account(mut, address = config.treasury)]
+    pub treasury: UncheckedAccount<'info>,
+    /// CHECK: validated against round.rent_payer
+    #[account(mut, address = round.rent_payer)]
+    pub rent_payer: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"insurance"], bump = insurance_fund.bump)]
+    pub insurance_fund: Option<Account<'info, InsuranceFund>>,
 }
 
 #[derive(Accounts)]
-#[instruction(side: u8)]
-pub struct JoinRound<'info> {
+pub struct ClaimMany<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
     #[account(seeds = [b"config"], bump = config.bump)]
     pub config: Account<'info, GlobalConfig>,
+    /// CHECK: `[b"referral", user]` PDA, deserialized in handler when initialized
+    #[account(seeds = [b"referral", user.key().as_ref()], bump)]
+    pub referral: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"referrer_rewards", referrer_rewards.referrer.as_ref()],
+        bump = referrer_rewards.bump
+    )]
+    pub referrer_rewards: Option<Account<'info, ReferrerRewards>>,
+    #[account(mut, seeds = [b"user_stats", user.key().as_ref()], bump = user_stats.bump)]
+    pub user_stats: Account<'info, UserStats>,
     #[account(mut)]
-    pub round: Account<'info, Round>,
+    pub season: Option<Account<'info, Season>>,
+    /// CHECK: `[b"season_stats", season, user]` PDA, validated and created in handler
+    #[account(mut)]
+    pub season_stats: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+#[instruction(duel_id: u64)]
+pub struct CreateDuel<'info> {
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
     #[account(
-        init_if_needed,
-        payer = user,
-        space = 8 + Position::INIT_SPACE,
-        seeds = [b"position", round.key().as_ref(), user.key().as_ref(), &[side]],
+        init,
+        payer = challenger,
+        space = 8 + Duel::INIT_SPACE,
+        seeds = [b"duel", challenger.key().as_ref(), duel_id.to_le_bytes().as_ref()],
         bump
     )]
-    pub position: Account<'info, Position>,
+    pub duel: Account<'info, Duel>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptDuel<'info> {
+    #[account(mut)]
+    pub counterparty: Signer<'info>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
     #[account(
         mut,
-        seeds = [b"vault", round.key().as_ref(), &[side]],
-        bump = side_vault.bump,
-        constraint = side_vault.round == round.key() @ PanchoError::VaultRoundMismatch,
-        constraint = side_vault.side == side @ PanchoError::InvalidSide
+        seeds = [b"duel", duel.challenger.as_ref(), duel.duel_id.to_le_bytes().as_ref()],
+        bump = duel.bump
     )]
-    pub side_vault: Account<'info, Vault>,
+    pub duel: Account<'info, Duel>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct LockRound<'info> {
+pub struct LockDuel<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(
+        mut,
+        seeds = [b"duel", duel.challenger.as_ref(), duel.duel_id.to_le_bytes().as_ref()],
+        bump = duel.bump
+    )]
+    pub duel: Account<'info, Duel>,
+    /// CHECK: validated in handler
+    pub oracle_price: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SettleDuel<'info> {
     #[account(seeds = [b"config"], bump = config.bump)]
     pub config: Account<'info, GlobalConfig>,
+    #[account(
+        mut,
+        close = challenger,
+        seeds = [b"duel", duel.challenger.as_ref(), duel.duel_id.to_le_bytes().as_ref()],
+        bump = duel.bump,
+        has_one = challenger,
+        has_one = counterparty
+    )]
+    pub duel: Account<'info, Duel>,
+    /// CHECK: validated against duel.challenger
     #[account(mut)]
-    pub round: Account<'info, Round>,
+    pub challenger: UncheckedAccount<'info>,
+    /// CHECK: validated against duel.counterparty
+    #[account(mut)]
+    pub counterparty: UncheckedAccount<'info>,
     /// CHECK: validated in handler
     pub oracle_price: UncheckedAccount<'info>,
+    /// CHECK: validated against config.treasury
+    #[account(mut, address = config.treasury)]
+    pub treasury: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelDuel<'info> {
+    #[account(
+        mut,
+        close = challenger,
+        seeds = [b"duel", duel.challenger.as_ref(), duel.duel_id.to_le_bytes().as_ref()],
+        bump = duel.bump,
+        has_one = challenger
+    )]
+    pub duel: Account<'info, Duel>,
+    /// CHECK: validated against duel.challenger
+    #[account(mut)]
+    pub challenger: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RefundDuel<'info> {
+    #[account(
+        mut,
+        close = challenger,
+        seeds = [b"duel", duel.challenger.as_ref(), duel.duel_id.to_le_bytes().as_ref()],
+        bump = duel.bump,
+        has_one = challenger,
+        has_one = counterparty
+    )]
+    pub duel: Account<'info, Duel>,
+    /// CHECK: validated against duel.challenger
+    #[account(mut)]
+    pub challenger: UncheckedAccount<'info>,
+    /// CHECK: validated against duel.counterparty
+    #[account(mut)]
+    pub counterparty: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(offer_id: u64)]
+pub struct PostOffer<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(mut)]
+    pub round: Account<'info, Round>,
+    #[account(
+        init,
+        payer = maker,
+        space = 8 + Offer::INIT_SPACE,
+        seeds = [b"offer", round.key().as_ref(), maker.key().as_ref(), offer_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub offer: Account<'info, Offer>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FillOffer<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(mut, address = offer.round @ PanchoError::OfferRoundMismatch)]
+    pub round: Account<'info, Round>,
+    #[account(
+        mut,
+        seeds = [b"offer", offer.round.as_ref(), offer.maker.as_ref(), offer.offer_id.to_le_bytes().as_ref()],
+        bump = offer.bump
+    )]
+    pub offer: Account<'info, Offer>,
+    #[account(
+        init,
+        payer = taker,
+        space = 8 + Contract::INIT_SPACE,
+        seeds = [b"contract", offer.key().as_ref(), offer.fill_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub contract: Account<'info, Contract>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct SettleRound<'info> {
-    #[account(seeds = [b"config"], bump = config.bump)]
-    pub config: Account<'info, GlobalConfig>,
-    #[account(mut)]
+pub struct SettleContract<'info> {
+    #[account(mut, address = contract.round @ PanchoError::OfferRoundMismatch)]
     pub round: Account<'info, Round>,
     #[account(
         mut,
-        seeds = [b"vault", round.key().as_ref(), &[SIDE_UP]],
-        bump = up_vault.bump,
-        constraint = up_vault.round == round.key() @ PanchoError::VaultRoundMismatch
+        close = taker,
+        has_one = maker,
+        has_one = taker
     )]
-    pub up_vault: Account<'info, Vault>,
+    pub contract: Account<'info, Contract>,
+    /// CHECK: validated against contract.maker
+    #[account(mut)]
+    pub maker: UncheckedAccount<'info>,
+    /// CHECK: validated against contract.taker
+    #[account(mut)]
+    pub taker: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseOffer<'info> {
+    #[account(mut, address = offer.round @ PanchoError::OfferRoundMismatch)]
+    pub round: Account<'info, Round>,
     #[account(
         mut,
-        seeds = [b"vault", round.key().as_ref(), &[SIDE_DOWN]],
-        bump = down_vault.bump,
-        constraint = down_vault.round == round.key() @ PanchoError::VaultRoundMismatch
+        close = maker,
+        seeds = [b"offer", offer.round.as_ref(), offer.maker.as_ref(), offer.offer_id.to_le_bytes().as_ref()],
+        bump = offer.bump,
+        has_one = maker
     )]
-    pub down_vault: Account<'info, Vault>,
-    /// CHECK: validated in handler
-    pub oracle_price: UncheckedAccount<'info>,
-    /// CHECK: validated against config.treasury
-    #[account(mut, address = config.treasury)]
-    pub treasury: UncheckedAccount<'info>,
+    pub offer: Account<'info, Offer>,
+    /// CHECK: validated against offer.maker
+    #[account(mut)]
+    pub maker: UncheckedAccount<'info>,
 }
 
 #[derive(Accounts)]
-pub struct Claim<'info> {
+#[instruction(referrer: Pubkey)]
+pub struct SetReferrer<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
-    #[account(mut)]
-    pub round: Account<'info, Round>,
     #[account(
-        mut,
-        seeds = [b"position", round.key().as_ref(), user.key().as_ref(), &[position.side]],
-        bump = position.bump,
-        constraint = position.round == round.key() @ PanchoError::PositionRoundMismatch,
-        constraint = position.user == user.key() @ PanchoError::PositionUserMismatch
+        init,
+        payer = user,
+        space = 8 + Referral::INIT_SPACE,
+        seeds = [b"referral", user.key().as_ref()],
+        bump
     )]
-    pub position: Account<'info, Position>,
+    pub referral: Account<'info, Referral>,
     #[account(
-        mut,
-        seeds = [b"vault", round.key().as_ref(), &[SIDE_UP]],
-        bump = up_vault.bump,
-        constraint = up_vault.round == round.key() @ PanchoError::VaultRoundMismatch
+        init_if_needed,
+        payer = user,
+        space = 8 + ReferrerRewards::INIT_SPACE,
+        seeds = [b"referrer_rewards", referrer.as_ref()],
+        bump
     )]
-    pub up_vault: Account<'info, Vault>,
+    pub referrer_rewards: Account<'info, ReferrerRewards>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimReferralRewards<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
     #[account(
         mut,
-        seeds = [b"vault", round.key().as_ref(), &[SIDE_DOWN]],
-        bump = down_vault.bump,
-        constraint = down_vault.round == round.key() @ PanchoError::VaultRoundMismatch
+        seeds = [b"referrer_rewards", referrer.key().as_ref()],
+        bump = referrer_rewards.bump,
+        has_one = referrer
     )]
-    pub down_vault: Account<'info, Vault>,
+    pub referrer_rewards: Account<'info, ReferrerRewards>,
 }
 
 #[account]
@@ -734,55 +5460,460 @@ pub struct GlobalConfig {
     pub oracle_max_age_sec: u32,
     pub paused: bool,
     pub bump: u8,
+    pub crank_tip_bps: u16,
+    pub crank_tip_lamports: u64,
+    pub claim_window_seconds: i64,
+    pub exit_fee_bps: u16,
+    pub switch_fee_bps: u16,
+    pub max_position_lamports: u64,
+    pub max_round_lamports: u64,
+    pub min_side_participants: u32,
+    pub min_pool_lamports: u64,
+    pub entry_cutoff_seconds_sol: u32,
+    pub entry_cutoff_seconds_btc: u32,
+    pub entry_cutoff_seconds_eth: u32,
+    pub time_weighted_rounds: bool,
+    pub time_weight_floor_bps: u16,
+    pub referral_fee_bps: u16,
+    pub fee_split_count: u8,
+    pub fee_split_recipients: [Pubkey; MAX_FEE_RECIPIENTS],
+    pub fee_split_bps: [u16; MAX_FEE_RECIPIENTS],
+    pub insurance_fee_bps: u16,
+    pub sweep_to_insurance: bool,
+    pub fee_tier_count: u8,
+    pub fee_tier_volume_lamports: [u64; MAX_FEE_TIERS],
+    pub fee_tier_bps: [u16; MAX_FEE_TIERS],
+    pub active_season: Pubkey,
+    pub commit_reveal_rounds: bool,
+    pub forfeit_unrevealed: bool,
+    pub oracle_kinds: [u8; MARKET_COUNT],
+    pub oracle_max_age_seconds: u32,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Round {
+    pub round_id: i64,
+    pub market: u8,
+    pub feed_id: [u8; 32],
+    pub oracle_price_account: Pubkey,
+    pub lock_ts: i64,
+    pub end_ts: i64,
+    pub start_price: i64,
+    pub end_price: i64,
+    pub expo: i32,
+    pub status: u8,
+    pub winner_side: u8,
+    pub up_total: u64,
+    pub down_total: u64,
+    pub fee_lamports: u64,
+    pub distributable_lamports: u64,
+    pub bump: u8,
+    pub tips_paid_lamports: u64,
+    pub settled_ts: i64,
+    pub rent_payer: Pubkey,
+    pub up_count: u32,
+    pub down_count: u32,
+    pub entry_cutoff_ts: i64,
+    pub time_weighted: bool,
+    pub open_ts: i64,
+    pub time_weight_floor_bps: u16,
+    pub up_weight: u64,
+    pub down_weight: u64,
+    pub referral_paid_lamports: u64,
+    pub up_chips: u64,
+    pub down_chips: u64,
+    pub room: Pubkey,
+    pub room_host: Pubkey,
+    pub host_fee_bps: u16,
+    pub host_fee_lamports: u64,
+    pub commit_reveal: bool,
+    pub committed_lamports: u64,
+    pub revealed_lamports: u64,
+    pub oracle_kind: u8,
+    pub open_accounts: u32,
+    pub settled_fee_bps: u16,
+    pub settled_fee_lamports: u64,
+    pub forfeit_unrevealed: bool,
+    pub claimed_positions: u32,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Vault {
+    pub round: Pubkey,
+    pub side: u8,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Position {
+    pub round: Pubkey,
+    pub user: Pubkey,
+    pub side: u8,
+    pub amount: u64,
+    pub claimed: bool,
+    pub bump: u8,
+    pub weight: u64,
+    pub insured_lamports: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct JoinLeg {
+    pub side: u8,
+    pub lamports: u64,
+    pub min_multiplier_bps: Option<u64>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct InsuranceFund {
+    pub total_deposited_lamports: u64,
+    pub total_paid_out_lamports: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct UserStats {
+    pub user: Pubkey,
+    pub last_volume_day: i64,
+    pub daily_volume_lamports: [u64; VOLUME_WINDOW_DAYS],
+    pub bump: u8,
+    pub total_staked_lamports: u64,
+    pub total_payout_lamports: u64,
+    pub rounds_played: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub voids: u32,
+    pub current_streak: u32,
+    pub best_streak: u32,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct UserStatsSnapshot {
+    pub user: Pubkey,
+    pub volume_30d_lamports: u64,
+    pub total_staked_lamports: u64,
+    pub total_payout_lamports: u64,
+    pub rounds_played: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub voids: u32,
+    pub current_streak: u32,
+    pub best_streak: u32,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Season {
+    pub season_id: u32,
+    pub start_ts: i64,
+    pub end_ts: i64,
+    pub table: RankedTable,
+    pub bump: u8,
+}
+
+// Same byte layout as the flat prize fields it replaced on `Season` and `Tournament`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct RankedTable {
+    pub prize_pool_lamports: u64,
+    pub prize_bps: [u16; LEADERBOARD_SIZE],
+    pub entry_count: u8,
+    pub entries: [RankEntry; LEADERBOARD_SIZE],
+    pub prize_claimed: [bool; LEADERBOARD_SIZE],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct RankEntry {
+    pub user: Pubkey,
+    pub score: i64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Commitment {
+    pub round: Pubkey,
+    pub user: Pubkey,
+    pub commitment_hash: [u8; 32],
+    pub lamports: u64,
+    pub committed_ts: i64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Room {
+    pub host: Pubkey,
+    pub room_id: u32,
+    pub allowlist_root: [u8; 32],
+    pub stake_lamports: u64,
+    pub host_fee_bps: u16,
+    pub round_count: u32,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Duel {
+    pub challenger: Pubkey,
+    pub counterparty: Pubkey,
+    pub duel_id: u64,
+    pub market: u8,
+    pub challenger_side: u8,
+    pub stake_lamports: u64,
+    pub expiry_ts: i64,
+    pub duration_seconds: i64,
+    pub status: u8,
+    pub start_price: i64,
+    pub expo: i32,
+    pub lock_ts: i64,
+    pub end_ts: i64,
+    pub bump: u8,
+    pub oracle_kind: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Offer {
+    pub round: Pubkey,
+    pub maker: Pubkey,
+    pub offer_id: u64,
+    pub side: u8,
+    pub odds_bps: u32,
+    pub maker_lamports: u64,
+    pub remaining_lamports: u64,
+    pub fill_count: u32,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Contract {
+    pub offer: Pubkey,
+    pub round: Pubkey,
+    pub maker: Pubkey,
+    pub taker: Pubkey,
+    pub taker_side: u8,
+    pub taker_lamports: u64,
+    pub maker_lamports: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Tournament {
+    pub tournament_id: u32,
+    pub entry_fee_lamports: u64,
+    pub starting_chips: u64,
+    pub markets_mask: u8,
+    pub start_ts: i64,
+    pub end_ts: i64,
+    pub entrant_count: u32,
+    pub table: RankedTable,
+    pub bump: u8,
+    pub open_entrants: u32,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Entrant {
+    pub tournament: Pubkey,
+    pub user: Pubkey,
+    pub chips: u64,
+    pub bump: u8,
+    pub open_chip_positions: u32,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ChipPosition {
+    pub round: Pubkey,
+    pub entrant: Pubkey,
+    pub side: u8,
+    pub chips: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct SeasonStats {
+    pub season: Pubkey,
+    pub user: Pubkey,
+    pub pnl_lamports: i64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct HouseVault {
+    pub enabled: bool,
+    pub seed_cap_lamports: u64,
+    pub total_funded_lamports: u64,
+    pub total_withdrawn_lamports: u64,
+    pub seeded_lamports: [u64; MARKET_COUNT],
+    pub returned_lamports: [u64; MARKET_COUNT],
+    pub pnl_lamports: [i64; MARKET_COUNT],
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Referral {
+    pub user: Pubkey,
+    pub referrer: Pubkey,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ReferrerRewards {
+    pub referrer: Pubkey,
+    pub accrued_lamports: u64,
+    pub total_earned_lamports: u64,
+    pub bump: u8,
+}
+
+#[event]
+pub struct RoundCreated {
+    pub round: Pubkey,
+    pub round_id: i64,
+    pub market: u8,
+    pub lock_ts: i64,
+    pub end_ts: i64,
+    pub entry_cutoff_ts: i64,
+}
+
+#[event]
+pub struct RoomCreated {
+    pub room: Pubkey,
+    pub host: Pubkey,
+    pub room_id: u32,
+    pub stake_lamports: u64,
+    pub host_fee_bps: u16,
+}
+
+#[event]
+pub struct RoomHostPaid {
+    pub round: Pubkey,
+    pub room: Pubkey,
+    pub host: Pubkey,
+    pub lamports: u64,
+}
+
+#[event]
+pub struct DuelCreated {
+    pub duel: Pubkey,
+    pub challenger: Pubkey,
+    pub market: u8,
+    pub side: u8,
+    pub stake_lamports: u64,
+    pub expiry_ts: i64,
+    pub duration_seconds: i64,
+}
+
+#[event]
+pub struct DuelAccepted {
+    pub duel: Pubkey,
+    pub counterparty: Pubkey,
+    pub lock_ts: i64,
+    pub end_ts: i64,
+}
+
+#[event]
+pub struct DuelLocked {
+    pub duel: Pubkey,
+    pub start_price: i64,
+    pub expo: i32,
+    pub end_ts: i64,
+}
+
+#[event]
+pub struct DuelSettled {
+    pub duel: Pubkey,
+    pub winner: Pubkey,
+    pub start_price: i64,
+    pub end_price: i64,
+    pub payout: u64,
+    pub fee: u64,
+    pub settled_at: i64,
+}
+
+#[event]
+pub struct DuelCancelled {
+    pub duel: Pubkey,
+    pub challenger: Pubkey,
+    pub refunded_lamports: u64,
+}
+
+#[event]
+pub struct DuelRefunded {
+    pub duel: Pubkey,
+    pub challenger: Pubkey,
+    pub counterparty: Pubkey,
+    pub refunded_lamports: u64,
+}
+
+#[event]
+pub struct OfferPosted {
+    pub offer: Pubkey,
+    pub round: Pubkey,
+    pub maker: Pubkey,
+    pub side: u8,
+    pub odds_bps: u32,
+    pub lamports: u64,
 }
 
-#[account]
-#[derive(InitSpace)]
-pub struct Round {
-    pub round_id: i64,
-    pub market: u8,
-    pub feed_id: [u8; 32],
-    pub oracle_price_account: Pubkey,
-    pub lock_ts: i64,
-    pub end_ts: i64,
-    pub start_price: i64,
-    pub end_price: i64,
-    pub expo: i32,
-    pub status: u8,
+#[event]
+pub struct OfferFilled {
+    pub offer: Pubkey,
+    pub contract: Pubkey,
+    pub taker: Pubkey,
+    pub taker_lamports: u64,
+    pub maker_lamports: u64,
+    pub remaining_lamports: u64,
+}
+
+#[event]
+pub struct ContractSettled {
+    pub contract: Pubkey,
+    pub round: Pubkey,
     pub winner_side: u8,
-    pub up_total: u64,
-    pub down_total: u64,
-    pub fee_lamports: u64,
-    pub distributable_lamports: u64,
-    pub bump: u8,
+    pub taker_payout: u64,
+    pub maker_payout: u64,
 }
 
-#[account]
-#[derive(InitSpace)]
-pub struct Vault {
+#[event]
+pub struct OfferClosed {
+    pub offer: Pubkey,
+    pub maker: Pubkey,
+    pub refunded_lamports: u64,
+}
+
+#[event]
+pub struct StakeCommitted {
     pub round: Pubkey,
-    pub side: u8,
-    pub bump: u8,
+    pub user: Pubkey,
+    pub lamports: u64,
 }
 
-#[account]
-#[derive(InitSpace)]
-pub struct Position {
+#[event]
+pub struct StakeRevealed {
     pub round: Pubkey,
     pub user: Pubkey,
     pub side: u8,
-    pub amount: u64,
-    pub claimed: bool,
-    pub bump: u8,
+    pub lamports: u64,
+    pub weight: u64,
 }
 
 #[event]
-pub struct RoundCreated {
+pub struct CommitmentForfeited {
     pub round: Pubkey,
-    pub round_id: i64,
-    pub market: u8,
-    pub lock_ts: i64,
-    pub end_ts: i64,
+    pub user: Pubkey,
+    pub lamports: u64,
+    pub forfeited: bool,
+    pub penalty_lamports: u64,
 }
 
 #[event]
@@ -791,6 +5922,27 @@ pub struct RoundJoined {
     pub user: Pubkey,
     pub side: u8,
     pub lamports: u64,
+    pub weight: u64,
+}
+
+#[event]
+pub struct PositionWithdrawn {
+    pub round: Pubkey,
+    pub user: Pubkey,
+    pub side: u8,
+    pub lamports: u64,
+    pub fee_lamports: u64,
+    pub remaining_stake: u64,
+}
+
+#[event]
+pub struct SideSwitched {
+    pub round: Pubkey,
+    pub user: Pubkey,
+    pub from_side: u8,
+    pub to_side: u8,
+    pub lamports: u64,
+    pub fee_lamports: u64,
 }
 
 #[event]
@@ -819,6 +5971,175 @@ pub struct Claimed {
     pub side: u8,
     pub stake: u64,
     pub payout: u64,
+    pub fee: u64,
+}
+
+#[event]
+pub struct CrankTipPaid {
+    pub round: Pubkey,
+    pub user: Pubkey,
+    pub cranker: Pubkey,
+    pub tip_lamports: u64,
+}
+
+#[event]
+pub struct PositionClosed {
+    pub round: Pubkey,
+    pub user: Pubkey,
+    pub side: u8,
+    pub stake: u64,
+    pub claimed: bool,
+    pub rent_lamports: u64,
+}
+
+#[event]
+pub struct RoundClosed {
+    pub round: Pubkey,
+    pub swept_lamports: u64,
+    pub rent_payer: Pubkey,
+    pub closed_at: i64,
+    pub protocol_fee_lamports: u64,
+}
+
+#[event]
+pub struct FeeDistributed {
+    pub round: Pubkey,
+    pub recipient: Pubkey,
+    pub lamports: u64,
+}
+
+#[event]
+pub struct InsuranceDeposited {
+    pub round: Pubkey,
+    pub lamports: u64,
+}
+
+#[event]
+pub struct InsurancePaidOut {
+    pub round: Pubkey,
+    pub recipient: Pubkey,
+    pub target: u8,
+    pub lamports: u64,
+}
+
+#[event]
+pub struct SeasonCreated {
+    pub season: Pubkey,
+    pub season_id: u32,
+    pub start_ts: i64,
+    pub end_ts: i64,
+}
+
+#[event]
+pub struct SeasonPrizeClaimed {
+    pub season: Pubkey,
+    pub user: Pubkey,
+    pub rank: u8,
+    pub lamports: u64,
+}
+
+#[event]
+pub struct SeasonClosed {
+    pub season: Pubkey,
+    pub swept_lamports: u64,
+}
+
+#[event]
+pub struct TournamentCreated {
+    pub tournament: Pubkey,
+    pub tournament_id: u32,
+    pub entry_fee_lamports: u64,
+    pub starting_chips: u64,
+    pub markets_mask: u8,
+    pub start_ts: i64,
+    pub end_ts: i64,
+}
+
+#[event]
+pub struct TournamentEntered {
+    pub tournament: Pubkey,
+    pub user: Pubkey,
+    pub chips: u64,
+}
+
+#[event]
+pub struct ChipsJoined {
+    pub round: Pubkey,
+    pub tournament: Pubkey,
+    pub user: Pubkey,
+    pub side: u8,
+    pub chips: u64,
+}
+
+#[event]
+pub struct ChipsClaimed {
+    pub round: Pubkey,
+    pub tournament: Pubkey,
+    pub user: Pubkey,
+    pub side: u8,
+    pub stake: u64,
+    pub payout: u64,
+}
+
+#[event]
+pub struct TournamentPrizeClaimed {
+    pub tournament: Pubkey,
+    pub user: Pubkey,
+    pub rank: u8,
+    pub lamports: u64,
+}
+
+#[event]
+pub struct TournamentClosed {
+    pub tournament: Pubkey,
+    pub swept_lamports: u64,
+}
+
+#[event]
+pub struct HouseVaultFunded {
+    pub lamports: u64,
+}
+
+#[event]
+pub struct HouseVaultWithdrawn {
+    pub lamports: u64,
+}
+
+#[event]
+pub struct HouseSeeded {
+    pub round: Pubkey,
+    pub market: u8,
+    pub side: u8,
+    pub lamports: u64,
+}
+
+#[event]
+pub struct HouseSettled {
+    pub round: Pubkey,
+    pub market: u8,
+    pub stake: u64,
+    pub payout: u64,
+    pub market_pnl_lamports: i64,
+}
+
+#[event]
+pub struct ReferralSet {
+    pub user: Pubkey,
+    pub referrer: Pubkey,
+}
+
+#[event]
+pub struct ReferralRewardAccrued {
+    pub round: Pubkey,
+    pub user: Pubkey,
+    pub referrer: Pubkey,
+    pub lamports: u64,
+}
+
+#[event]
+pub struct ReferralRewardsClaimed {
+    pub referrer: Pubkey,
+    pub lamports: u64,
 }
 
 #[error_code]
@@ -877,4 +6198,525 @@ pub enum PanchoError {
     InvalidOracleOwner,
     #[msg("Stale oracle price")]
     StaleOraclePrice,
+    #[msg("Invalid batch accounts")]
+    InvalidBatch,
+    #[msg("Invalid position account")]
+    InvalidPositionAccount,
+    #[msg("Position has an unclaimed payout")]
+    PositionNotClaimed,
+    #[msg("Claim window still open")]
+    ClaimWindowOpen,
+    #[msg("Position stake cap exceeded")]
+    PositionCapExceeded,
+    #[msg("Round pool cap exceeded")]
+    RoundCapExceeded,
+    #[msg("Pool odds moved past slippage tolerance")]
+    OddsSlippageExceeded,
+    #[msg("Entry cutoff passed")]
+    EntryCutoffPassed,
+    #[msg("Invalid referrer")]
+    InvalidReferrer,
+    #[msg("Referral accounts mismatch")]
+    ReferralAccountsMismatch,
+    #[msg("Invalid fee split")]
+    InvalidFeeSplit,
+    #[msg("Fee recipient mismatch")]
+    FeeRecipientMismatch,
+    #[msg("Insurance fund account required")]
+    InsuranceFundRequired,
+    #[msg("Invalid insurance payout target")]
+    InvalidInsuranceTarget,
+    #[msg("House liquidity accounts required")]
+    HouseAccountsRequired,
+    #[msg("House positions are claimed through claim_house")]
+    HouseClaimRequired,
+    #[msg("Invalid fee tiers")]
+    InvalidFeeTiers,
+    #[msg("Invalid season parameters")]
+    InvalidSeason,
+    #[msg("Active season accounts required")]
+    SeasonAccountsRequired,
+    #[msg("Season account mismatch")]
+    SeasonMismatch,
+    #[msg("Season already finalized")]
+    SeasonFinalized,
+    #[msg("Season not finalized yet")]
+    SeasonNotFinalized,
+    #[msg("No season prize for this user")]
+    NotSeasonWinner,
+    #[msg("Invalid tournament parameters")]
+    InvalidTournament,
+    #[msg("Round is not part of this tournament")]
+    TournamentRoundMismatch,
+    #[msg("Not enough tournament chips")]
+    InsufficientChips,
+    #[msg("Tournament already finalized")]
+    TournamentFinalized,
+    #[msg("Tournament not finalized yet")]
+    TournamentNotFinalized,
+    #[msg("Invalid room parameters")]
+    InvalidRoom,
+    #[msg("Room account required for room rounds")]
+    RoomRequired,
+    #[msg("Room account mismatch")]
+    RoomMismatch,
+    #[msg("Wallet is not on the room allowlist")]
+    NotAllowlisted,
+    #[msg("Room host account required")]
+    RoomHostRequired,
+    #[msg("Duel is not open")]
+    DuelNotOpen,
+    #[msg("Duel is not locked")]
+    DuelNotLocked,
+    #[msg("Duel offer expired")]
+    DuelExpired,
+    #[msg("Duel offer has not expired yet")]
+    DuelNotExpired,
+    #[msg("Challenger cannot accept their own duel")]
+    InvalidDuelCounterparty,
+    #[msg("Invalid offer odds")]
+    InvalidOdds,
+    #[msg("Fill exceeds the offer's remaining liquidity")]
+    OfferLiquidityExceeded,
+    #[msg("Offer round mismatch")]
+    OfferRoundMismatch,
+    #[msg("Makers cannot fill their own offer")]
+    InvalidOfferTaker,
+    #[msg("Offer can only be closed once the round locks")]
+    OfferStillOpen,
+    #[msg("Commit-reveal rounds take commit_stake instead of joins")]
+    CommitRevealRound,
+    #[msg("Round does not use commit-reveal")]
+    NotCommitRevealRound,
+    #[msg("Reveal does not match the commitment")]
+    InvalidReveal,
+    #[msg("Reveal window is closed")]
+    RevealWindowClosed,
+    #[msg("Reveal window is still open")]
+    RevealWindowOpen,
+    #[msg("Invalid oracle kind")]
+    InvalidOracleKind,
+    #[msg("Oracle price update is not fully verified")]
+    InsufficientOracleVerification,
+    #[msg("Round still has open positions")]
+    RoundHasOpenAccounts,
+    #[msg("Fee recipient is not rent-exempt")]
+    FeeRecipientNotRentExempt,
+    #[msg("Insurance payout exceeds what the position's claim falls short of its stake")]
+    InsurancePayoutExceedsStake,
+    #[msg("No prize for this user")]
+    NotPrizeWinner,
+    #[msg("Season has not ended yet")]
+    SeasonNotEnded,
+    #[msg("Tournament has not ended yet")]
+    TournamentNotEnded,
+    #[msg("Duel has not been accepted")]
+    DuelNotAccepted,
+    #[msg("Duel settle window has expired")]
+    DuelSettleWindowExpired,
+    #[msg("Duel cannot be refunded yet")]
+    DuelNotRefundable,
+    #[msg("Oracle price was published too late")]
+    OraclePriceTooLate,
+    #[msg("Oracle max age must be positive")]
+    InvalidOracleMaxAge,
+    #[msg("Config account is not a config owned by this admin")]
+    InvalidConfigAccount,
+    #[msg("Entrant still has unclaimed chip positions")]
+    EntrantHasOpenPositions,
+    #[msg("Tournament still has open entrants")]
+    TournamentHasOpenEntrants,
+    #[msg("Round pool cannot be topped up after claims")]
+    InsuranceAfterClaims,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zeroed<T: AnchorDeserialize + Space>() -> T {
+        T::deserialize(&mut &vec![0u8; T::INIT_SPACE][..]).unwrap()
+    }
+
+    fn account_bytes<T: AccountSerialize>(value: &T) -> Vec<u8> {
+        let mut data = Vec::new();
+        value.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    fn weighted_round() -> Round {
+        let mut round: Round = zeroed();
+        round.time_weighted = true;
+        round.open_ts = 0;
+        round.lock_ts = 1_000;
+        round.time_weight_floor_bps = 2_000;
+        round
+    }
+
+    #[test]
+    fn stake_weight_decays_to_floor() {
+        let round = weighted_round();
+        assert_eq!(stake_weight(&round, 1_000_000, 0).unwrap(), 1_000_000);
+        assert_eq!(stake_weight(&round, 1_000_000, 500).unwrap(), 600_000);
+        assert_eq!(stake_weight(&round, 1_000_000, 1_000).unwrap(), 200_000);
+
+        let mut flat = round;
+        flat.time_weighted = false;
+        assert_eq!(stake_weight(&flat, 1_000_000, 900).unwrap(), 1_000_000);
+    }
+
+    #[test]
+    fn implied_multiplier_uses_side_weight_on_weighted_rounds() {
+        let mut round = weighted_round();
+        round.up_total = 2_000;
+        round.down_total = 2_000;
+        round.up_weight = 1_000;
+        round.down_weight = 2_000;
+
+        // Untimed: 4000 / 2000 = 2x, less a 1% fee.
+        round.time_weighted = false;
+        assert_eq!(implied_multiplier_bps(&round, SIDE_UP, 1_000, 1_000, 100).unwrap(), 19_800);
+
+        // Weighted: a full-weight stake owns 1000 of 1000 up weight per 1000 lamports.
+        round.time_weighted = true;
+        assert_eq!(implied_multiplier_bps(&round, SIDE_UP, 1_000, 1_000, 0).unwrap(), 40_000);
+        assert_eq!(implied_multiplier_bps(&round, SIDE_UP, 1_000, 500, 0).unwrap(), 20_000);
+    }
+
+    #[test]
+    fn payout_splits_by_weight_and_falls_back_to_stake() {
+        let mut round = weighted_round();
+        round.winner_side = SIDE_UP;
+        round.up_total = 300;
+        round.down_total = 300;
+        round.up_weight = 150;
+        round.distributable_lamports = 600;
+
+        let mut position: Position = zeroed();
+        position.side = SIDE_UP;
+        position.amount = 100;
+        position.weight = 100;
+        assert_eq!(position_payout(&round, &position).unwrap(), 400);
+
+        round.up_weight = 0;
+        position.weight = 0;
+        assert_eq!(position_payout(&round, &position).unwrap(), 200);
+
+        position.side = SIDE_DOWN;
+        assert_eq!(position_payout(&round, &position).unwrap(), 0);
+    }
+
+    fn tiered_config() -> GlobalConfig {
+        let mut config: GlobalConfig = zeroed();
+        config.fee_bps = 600;
+        config.fee_tier_count = 2;
+        config.fee_tier_volume_lamports[..2].copy_from_slice(&[1_000, 10_000]);
+        config.fee_tier_bps[..2].copy_from_slice(&[400, 200]);
+        config
+    }
+
+    #[test]
+    fn claim_fee_follows_rolling_volume_tiers() {
+        let config = tiered_config();
+        let now = 100 * SECONDS_PER_DAY;
+        let mut user_stats: UserStats = zeroed();
+        user_stats.last_volume_day = 100;
+        assert_eq!(claim_fee_bps(&config, &user_stats, now), 600);
+
+        user_stats.daily_volume_lamports[volume_bucket(100)] = 3_000;
+        user_stats.daily_volume_lamports[volume_bucket(95)] = 2_000;
+        assert_eq!(rolling_volume(&user_stats, now), 5_000);
+        assert_eq!(claim_fee_bps(&config, &user_stats, now), 400);
+
+        // Thirty days later both buckets have aged out of the window.
+        assert_eq!(claim_fee_bps(&config, &user_stats, now + 30 * SECONDS_PER_DAY), 600);
+    }
+
+    #[test]
+    fn withdrawals_come_back_out_of_stats_and_volume() {
+        let now = 100 * SECONDS_PER_DAY;
+        let mut user_stats: UserStats = zeroed();
+        user_stats.last_volume_day = 100;
+        user_stats.total_staked_lamports = 5_000;
+        user_stats.rounds_played = 2;
+        user_stats.daily_volume_lamports[volume_bucket(100)] = 3_000;
+        user_stats.daily_volume_lamports[volume_bucket(99)] = 2_000;
+
+        record_withdrawal(&mut user_stats, 4_000, true, now);
+        assert_eq!(user_stats.total_staked_lamports, 1_000);
+        assert_eq!(user_stats.rounds_played, 1);
+        assert_eq!(user_stats.daily_volume_lamports[volume_bucket(100)], 0);
+        assert_eq!(rolling_volume(&user_stats, now), 1_000);
+
+        record_withdrawal(&mut user_stats, 9_000, false, now);
+        assert_eq!(user_stats.total_staked_lamports, 0);
+        assert_eq!(rolling_volume(&user_stats, now), 0);
+    }
+
+    #[test]
+    fn settle_charges_the_lowest_claimable_rate() {
+        let mut config = tiered_config();
+        assert_eq!(min_claim_fee_bps(&config), 200);
+        config.fee_tier_count = 0;
+        assert_eq!(min_claim_fee_bps(&config), 600);
+        config.fee_tier_count = 1;
+        config.fee_tier_bps[0] = 900;
+        assert_eq!(min_claim_fee_bps(&config), 600);
+    }
+
+    #[test]
+    fn ranked_table_keeps_the_top_scores() {
+        let mut table = RankedTable::default();
+        let users: Vec<Pubkey> = (0..=LEADERBOARD_SIZE).map(|_| Pubkey::new_unique()).collect();
+        for (i, user) in users.iter().take(LEADERBOARD_SIZE).enumerate() {
+            table.record(*user, (i as i64 + 1) * 100);
+        }
+        assert_eq!(table.entry_count as usize, LEADERBOARD_SIZE);
+        assert_eq!(table.entries[0].user, users[LEADERBOARD_SIZE - 1]);
+
+        // The leader's PnL drops; an unlisted player with a better score takes the last slot.
+        table.record(users[LEADERBOARD_SIZE - 1], -50);
+        table.record(users[LEADERBOARD_SIZE], 150);
+        let listed: Vec<Pubkey> = table.entries.iter().map(|entry| entry.user).collect();
+        assert!(listed.contains(&users[LEADERBOARD_SIZE]));
+        assert!(!listed.contains(&users[LEADERBOARD_SIZE - 1]));
+        assert!(table.entries.windows(2).all(|pair| pair[0].score >= pair[1].score));
+
+        // A score below the table is ignored.
+        table.record(Pubkey::new_unique(), 0);
+        assert_eq!(table.entries[LEADERBOARD_SIZE - 1].score, 100);
+    }
+
+    #[test]
+    fn ranked_prizes_follow_rank_and_pay_once() {
+        let mut table = RankedTable::new(&[7_000, 3_000]);
+        table.add_to_pool(1_000).unwrap();
+        let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+        table.record(first, 50);
+        table.record(second, 80);
+        table.record(first, 120);
+
+        assert_eq!(table.take_prize(first).unwrap(), (0, 700));
+        assert_eq!(table.take_prize(second).unwrap(), (1, 300));
+        assert!(table.take_prize(first).is_err());
+        assert!(table.take_prize(Pubkey::new_unique()).is_err());
+    }
+
+    #[test]
+    fn duels_refund_only_after_their_oracle_window() {
+        let mut duel: Duel = zeroed();
+        duel.lock_ts = 1_000;
+        duel.end_ts = 2_000;
+
+        duel.status = DUEL_OPEN;
+        assert!(!duel_refundable(&duel, i64::MAX));
+
+        duel.status = DUEL_ACCEPTED;
+        assert!(!duel_refundable(&duel, 1_000 + LOCK_GRACE_SECONDS));
+        assert!(duel_refundable(&duel, 1_001 + LOCK_GRACE_SECONDS));
+
+        duel.status = DUEL_LOCKED;
+        assert!(!duel_refundable(&duel, 2_000 + DUEL_SETTLE_GRACE_SECONDS));
+        assert!(duel_refundable(&duel, 2_001 + DUEL_SETTLE_GRACE_SECONDS));
+    }
+
+    #[test]
+    fn contracts_and_chips_resolve_from_prices_not_participation() {
+        let mut round: Round = zeroed();
+        round.winner_side = SIDE_NONE;
+        assert_eq!(price_winner_side(&round), SIDE_NONE);
+
+        round.start_price = 100;
+        round.end_price = 100;
+        assert_eq!(price_winner_side(&round), SIDE_NONE);
+        round.end_price = 90;
+        assert_eq!(price_winner_side(&round), SIDE_DOWN);
+        round.end_price = 110;
+        assert_eq!(price_winner_side(&round), SIDE_UP);
+
+        round.up_chips = 300;
+        round.down_chips = 100;
+        let mut position: ChipPosition = zeroed();
+        position.side = SIDE_UP;
+        position.chips = 150;
+        assert_eq!(chip_payout(&round, &position).unwrap(), 200);
+        position.side = SIDE_DOWN;
+        position.chips = 100;
+        assert_eq!(chip_payout(&round, &position).unwrap(), 0);
+    }
+
+    #[test]
+    fn reveal_window_is_short_and_capped_at_end() {
+        let mut round: Round = zeroed();
+        round.lock_ts = 1_000;
+        round.end_ts = 1_300;
+        assert_eq!(reveal_deadline(&round).unwrap(), 1_000 + REVEAL_WINDOW_SECONDS);
+        round.end_ts = 1_030;
+        assert_eq!(reveal_deadline(&round).unwrap(), 1_030);
+    }
+
+    #[test]
+    fn unrevealed_stakes_pay_the_fee_or_forfeit() {
+        let mut config: GlobalConfig = zeroed();
+        config.fee_bps = 250;
+        let mut round: Round = zeroed();
+        round.forfeit_unrevealed = false;
+        assert_eq!(unrevealed_penalty(&config, &round, 10_000).unwrap(), 250);
+        round.forfeit_unrevealed = true;
+        assert_eq!(unrevealed_penalty(&config, &round, 10_000).unwrap(), 10_000);
+    }
+
+    #[test]
+    fn commitment_hash_is_sha256_of_side_salt_user() {
+        // sha256(0x01 || [7; 32] || [9; 32]), the same vector as onchain/tests/pancho_pvp.ts.
+        let expected = [
+            155, 212, 121, 73, 75, 77, 142, 240, 240, 148, 182, 51, 208, 195, 196, 32, 55, 26, 84, 246, 71, 52, 37,
+            148, 134, 95, 227, 135, 94, 168, 119, 225,
+        ];
+        assert_eq!(commitment_hash(SIDE_DOWN, &[7; 32], Pubkey::new_from_array([9; 32])), expected);
+    }
+
+    #[test]
+    fn v2_rounds_settle_void_once_no_read_can_pass() {
+        let mut config: GlobalConfig = zeroed();
+        config.oracle_max_age_seconds = 60;
+        let mut round: Round = zeroed();
+        round.end_ts = 1_000;
+        let deadline = 1_000 + ORACLE_PUBLISH_TOLERANCE_SECONDS + 60;
+
+        assert!(!settle_read_expired(&config, &round, deadline + 1));
+        round.oracle_kind = ORACLE_KIND_PRICE_UPDATE_V2;
+        assert!(!settle_read_expired(&config, &round, deadline));
+        assert!(settle_read_expired(&config, &round, deadline + 1));
+    }
+
+    #[test]
+    fn referral_share_goes_only_to_the_recorded_referrer() {
+        let mut config: GlobalConfig = zeroed();
+        config.referral_fee_bps = 1_000;
+        let mut round: Round = zeroed();
+        let mut position: Position = zeroed();
+        position.user = Pubkey::new_unique();
+        let referrer = Pubkey::new_unique();
+
+        let keys: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let (mut referral_lamports, mut rewards_lamports, mut up_lamports, mut down_lamports) =
+            (1, 1, 60, 1_000);
+        let mut referral_data = account_bytes(&Referral {
+            user: position.user,
+            referrer,
+            bump: 0,
+        });
+        let mut rewards_data = account_bytes(&ReferrerRewards {
+            referrer,
+            accrued_lamports: 0,
+            total_earned_lamports: 0,
+            bump: 0,
+        });
+        let (mut up_data, mut down_data) = ([0u8; 0], [0u8; 0]);
+        let referral = AccountInfo::new(&keys[0], false, false, &mut referral_lamports, &mut referral_data, &crate::ID, false, 0);
+        let rewards_info = AccountInfo::new(&keys[1], false, true, &mut rewards_lamports, &mut rewards_data, &crate::ID, false, 0);
+        let up_vault = AccountInfo::new(&keys[2], false, true, &mut up_lamports, &mut up_data, &crate::ID, false, 0);
+        let down_vault = AccountInfo::new(&keys[3], false, true, &mut down_lamports, &mut down_data, &crate::ID, false, 0);
+        let mut rewards: Account<ReferrerRewards> = Account::try_from(&rewards_info).unwrap();
+
+        pay_referral_share(&config, &mut round, &position, 1_000, &referral, Some(&mut rewards), &up_vault, &down_vault)
+            .unwrap();
+        assert_eq!(rewards.accrued_lamports, 100);
+        assert_eq!(round.referral_paid_lamports, 100);
+        assert_eq!(up_vault.lamports() + down_vault.lamports(), 960);
+        assert_eq!(rewards_info.lamports(), 101);
+
+        // Initialized referral without its rewards account, or with someone else's.
+        assert!(
+            pay_referral_share(&config, &mut round, &position, 1_000, &referral, None, &up_vault, &down_vault).is_err()
+        );
+        rewards.referrer = Pubkey::new_unique();
+        assert!(pay_referral_share(&config, &mut round, &position, 1_000, &referral, Some(&mut rewards), &up_vault, &down_vault)
+            .is_err());
+
+        // No referrer: the PDA is still passed, but uninitialized.
+        let (empty_key, mut empty_lamports, mut empty_data) = (Pubkey::new_unique(), 0, [0u8; 0]);
+        let system_program_id = anchor_lang::system_program::ID;
+        let empty = AccountInfo::new(&empty_key, false, false, &mut empty_lamports, &mut empty_data, &system_program_id, false, 0);
+        pay_referral_share(&config, &mut round, &position, 1_000, &empty, None, &up_vault, &down_vault).unwrap();
+        assert_eq!(round.referral_paid_lamports, 100);
+    }
+
+    #[test]
+    fn insurance_covers_only_the_claim_shortfall() {
+        let mut round: Round = zeroed();
+        round.up_total = 300;
+        round.down_total = 300;
+        round.distributable_lamports = 540;
+        let mut position: Position = zeroed();
+        position.side = SIDE_UP;
+        position.amount = 100;
+
+        // Void: the refund is 90 of 100 staked.
+        round.winner_side = SIDE_NONE;
+        assert_eq!(insurable_shortfall(&round, &position).unwrap(), 10);
+
+        // Losing side: the whole stake.
+        round.winner_side = SIDE_DOWN;
+        assert_eq!(insurable_shortfall(&round, &position).unwrap(), 100);
+
+        // Winning side: 180 back on 100, nothing to cover.
+        round.winner_side = SIDE_UP;
+        assert_eq!(insurable_shortfall(&round, &position).unwrap(), 0);
+    }
+
+    fn price_update_v2_bytes(verification: &[u8], feed_id: [u8; 32], price: i64, expo: i32, publish_time: i64) -> Vec<u8> {
+        let mut data = PRICE_UPDATE_V2_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&[7u8; 32]); // write authority
+        data.extend_from_slice(verification);
+        data.extend_from_slice(&feed_id);
+        data.extend_from_slice(&price.to_le_bytes());
+        data.extend_from_slice(&25u64.to_le_bytes()); // conf
+        data.extend_from_slice(&expo.to_le_bytes());
+        data.extend_from_slice(&publish_time.to_le_bytes());
+        data.extend_from_slice(&(publish_time - 1).to_le_bytes()); // prev_publish_time
+        data.extend_from_slice(&[0u8; 16]); // ema_price, ema_conf
+        data.extend_from_slice(&99u64.to_le_bytes()); // posted_slot
+        data
+    }
+
+    #[test]
+    fn price_update_v2_parses_both_verification_levels() {
+        let feed_id = [3u8; 32];
+        let full = parse_price_update_v2_account(&price_update_v2_bytes(
+            &[PRICE_UPDATE_V2_VERIFICATION_FULL],
+            feed_id,
+            6_512_345,
+            -5,
+            1_700_000_000,
+        ))
+        .unwrap();
+        assert!(full.fully_verified);
+        assert_eq!(full.feed_id, feed_id);
+        assert_eq!((full.price, full.expo, full.publish_time), (6_512_345, -5, 1_700_000_000));
+
+        let partial = parse_price_update_v2_account(&price_update_v2_bytes(
+            &[PRICE_UPDATE_V2_VERIFICATION_PARTIAL, 3],
+            feed_id,
+            -42,
+            -8,
+            1_700_000_060,
+        ))
+        .unwrap();
+        assert!(!partial.fully_verified);
+        assert_eq!(partial.feed_id, feed_id);
+        assert_eq!((partial.price, partial.expo, partial.publish_time), (-42, -8, 1_700_000_060));
+
+        let mut bad_discriminator =
+            price_update_v2_bytes(&[PRICE_UPDATE_V2_VERIFICATION_FULL], feed_id, 1, -5, 1_700_000_000);
+        bad_discriminator[0] ^= 1;
+        assert!(parse_price_update_v2_account(&bad_discriminator).is_none());
+
+        let mut bad_level = price_update_v2_bytes(&[2], feed_id, 1, -5, 1_700_000_000);
+        assert!(parse_price_update_v2_account(&bad_level).is_none());
+        bad_level.truncate(PRICE_UPDATE_V2_OFFSET_VERIFICATION + 40);
+        bad_level[PRICE_UPDATE_V2_OFFSET_VERIFICATION] = PRICE_UPDATE_V2_VERIFICATION_FULL;
+        assert!(parse_price_update_v2_account(&bad_level).is_none());
+    }
 }
@@ -1,4 +1,69 @@
 import * as anchor from "@coral-xyz/anchor";
+import { strict as assert } from "node:assert";
+import { createHash } from "node:crypto";
+import { readFileSync } from "node:fs";
+import path from "node:path";
+
+const idl = JSON.parse(readFileSync(path.join(__dirname, "..", "abi", "pancho_pvp.idl.json"), "utf8"));
+
+const PRIMITIVE_SIZES: Record<string, number> = {
+  bool: 1,
+  u8: 1,
+  i8: 1,
+  u16: 2,
+  i16: 2,
+  u32: 4,
+  i32: 4,
+  u64: 8,
+  i64: 8,
+  u128: 16,
+  i128: 16,
+  pubkey: 32
+};
+
+function typeSize(type: any): number {
+  if (typeof type === "string") {
+    const size = PRIMITIVE_SIZES[type];
+    assert.ok(size !== undefined, `unsized type ${type}`);
+    return size;
+  }
+  if (type.array) {
+    return typeSize(type.array[0]) * type.array[1];
+  }
+  throw new Error(`unsupported type ${JSON.stringify(type)}`);
+}
+
+// Byte offset of a field inside an account, including the 8-byte discriminator. The
+// keeper and the frontend decode accounts by these offsets, so fields must only ever
+// be appended.
+function fieldOffset(account: string, field: string): number {
+  const def = idl.types.find((t: any) => t.name === account);
+  assert.ok(def, `missing type ${account}`);
+  let offset = 8;
+  for (const f of def.type.fields) {
+    if (f.name === field) {
+      return offset;
+    }
+    offset += typeSize(f.type);
+  }
+  throw new Error(`missing field ${account}.${field}`);
+}
+
+function instruction(name: string) {
+  const ix = idl.instructions.find((i: any) => i.name === name);
+  assert.ok(ix, `missing instruction ${name}`);
+  return ix;
+}
+
+function accountNames(name: string): string[] {
+  return instruction(name).accounts.map((a: any) => a.name);
+}
+
+function isWritable(name: string, account: string): boolean {
+  const a = instruction(name).accounts.find((acc: any) => acc.name === account);
+  assert.ok(a, `missing account ${name}.${account}`);
+  return Boolean(a.writable);
+}
 
 describe("pancho_pvp", () => {
   const provider = anchor.AnchorProvider.env();
@@ -9,4 +74,61 @@ describe("pancho_pvp", () => {
       throw new Error("Missing test wallet");
     }
   });
+
+  it("keeps the offsets the keeper decodes", () => {
+    assert.equal(fieldOffset("Round", "lock_ts"), 81);
+    assert.equal(fieldOffset("Round", "end_ts"), 89);
+    assert.equal(fieldOffset("Round", "status"), 117);
+    assert.equal(fieldOffset("Round", "up_total"), 119);
+    assert.equal(fieldOffset("Round", "down_total"), 127);
+    assert.equal(fieldOffset("Round", "oracle_kind"), 358);
+    assert.equal(fieldOffset("GlobalConfig", "fee_bps"), 200);
+    assert.equal(fieldOffset("GlobalConfig", "fee_split_count"), 275);
+    assert.equal(fieldOffset("GlobalConfig", "insurance_fee_bps"), 446);
+    assert.equal(fieldOffset("GlobalConfig", "active_season"), 500);
+    assert.equal(fieldOffset("GlobalConfig", "oracle_kinds"), 534);
+    assert.equal(fieldOffset("GlobalConfig", "oracle_max_age_seconds"), 537);
+
+    const config = idl.accounts.find((a: any) => a.name === "GlobalConfig");
+    assert.deepEqual(config.discriminator, [149, 8, 156, 202, 160, 252, 176, 217]);
+  });
+
+  it("locks duels after acceptance and refunds what the oracle never settles", () => {
+    assert.ok(!accountNames("accept_duel").includes("oracle_price"));
+    assert.deepEqual(accountNames("lock_duel"), ["config", "duel", "oracle_price"]);
+    assert.ok(accountNames("settle_duel").includes("oracle_price"));
+    for (const account of ["duel", "challenger", "counterparty"]) {
+      assert.ok(isWritable("refund_duel", account));
+    }
+    assert.ok(isWritable("cancel_duel", "challenger"));
+  });
+
+  it("counts offers and contracts on the round", () => {
+    for (const name of ["post_offer", "fill_offer", "settle_contract", "close_offer"]) {
+      assert.ok(isWritable(name, "round"), `${name} must update open_accounts`);
+    }
+    assert.ok(!accountNames("settle_contract").includes("oracle_price"));
+  });
+
+  it("reveals a stake against sha256(side || salt || user)", () => {
+    assert.deepEqual(
+      instruction("commit_stake").args.map((a: any) => a.name),
+      ["commitment_hash", "lamports"]
+    );
+    assert.deepEqual(
+      instruction("reveal_stake").args.map((a: any) => a.name),
+      ["side", "salt"]
+    );
+    for (const name of ["commit_stake", "reveal_stake", "forfeit_commitment"]) {
+      assert.ok(isWritable(name, "round"), `${name} must update open_accounts`);
+    }
+
+    // reveal_stake recomputes hashv(&[&[side], salt, user]); the program's
+    // commitment_hash_is_sha256_of_side_salt_user test pins the same vector.
+    const side = 1;
+    const salt = Buffer.alloc(32, 7);
+    const user = new anchor.web3.PublicKey(Buffer.alloc(32, 9));
+    const hash = createHash("sha256").update(Buffer.from([side])).update(salt).update(user.toBuffer()).digest();
+    assert.equal(hash.toString("hex"), "9bd479494b4d8ef0f094b633d0c3c420371a54f647342594865fe3875ea877e1");
+  });
 });
@@ -25,19 +25,22 @@ const MARKETS = [
     key: "SOL",
     code: 0,
     feedIdHex: "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d",
-    oraclePriceAccountEnv: "PANCHO_ORACLE_ACCOUNT_SOL"
+    oraclePriceAccountEnv: "PANCHO_ORACLE_ACCOUNT_SOL",
+    priceUpdateAccountEnv: "PANCHO_PRICE_UPDATE_ACCOUNT_SOL"
   },
   {
     key: "BTC",
     code: 1,
     feedIdHex: "e62df6c8b4a85fe1a67db44dc12de5db330f7ac66b72dc658afedf0f4a415b43",
-    oraclePriceAccountEnv: "PANCHO_ORACLE_ACCOUNT_BTC"
+    oraclePriceAccountEnv: "PANCHO_ORACLE_ACCOUNT_BTC",
+    priceUpdateAccountEnv: "PANCHO_PRICE_UPDATE_ACCOUNT_BTC"
   },
   {
     key: "ETH",
     code: 2,
     feedIdHex: "ff61491a931112ddf1bd8147cd1b641375f79f5825126d665480874634fd0ace",
-    oraclePriceAccountEnv: "PANCHO_ORACLE_ACCOUNT_ETH"
+    oraclePriceAccountEnv: "PANCHO_ORACLE_ACCOUNT_ETH",
+    priceUpdateAccountEnv: "PANCHO_PRICE_UPDATE_ACCOUNT_ETH"
   }
 ];
 
@@ -46,6 +49,9 @@ const ROUND_ACCOUNT_DISCRIMINATOR = createDiscriminator("account:Round");
 const ROUND_STATUS_OPEN = 0;
 const ROUND_STATUS_LOCKED = 1;
 const ROUND_STATUS_SETTLED = 2;
+const ORACLE_KIND_PRICE_UPDATE_V2 = 1;
+const INSURANCE_FUND_PDA = PublicKey.findProgramAddressSync([Buffer.from("insurance")], PROGRAM_ID)[0];
+const HOUSE_VAULT_PDA = PublicKey.findProgramAddressSync([Buffer.from("house")], PROGRAM_ID)[0];
 
 function createDiscriminator(label) {
   return createHash("sha256").update(label).digest().subarray(0, 8);
@@ -155,10 +161,26 @@ async function fetchConfig(connection, configPda) {
     oracleAccountEth: new PublicKey(data.subarray(168, 200)),
     feeBps: data.readUInt16LE(200),
     oracleMaxAgeSlots: data.readUInt32LE(202),
-    paused: data.readUInt8(206) === 1
+    paused: data.readUInt8(206) === 1,
+    feeSplitRecipients: parseFeeSplitRecipients(data),
+    insuranceFeeBps: data.length >= 449 ? data.readUInt16LE(446) : 0
   };
 }
 
+// Fee split table lives at the tail of GlobalConfig: count (u8) then 5 recipient pubkeys.
+function parseFeeSplitRecipients(data) {
+  if (data.length < 436) {
+    return [];
+  }
+  const count = Math.min(data.readUInt8(275), 5);
+  const recipients = [];
+  for (let i = 0; i < count; i += 1) {
+    const start = 276 + i * 32;
+    recipients.push(new PublicKey(data.subarray(start, start + 32)));
+  }
+  return recipients;
+}
+
 function parseRound(data) {
   const buf = Buffer.from(data);
   if (buf.length < 160 || !buf.subarray(0, 8).equals(ROUND_ACCOUNT_DISCRIMINATOR)) {
@@ -169,7 +191,10 @@ function parseRound(data) {
     market: buf.readUInt8(16),
     lockTs: Number(buf.readBigInt64LE(81)),
     endTs: Number(buf.readBigInt64LE(89)),
-    status: buf.readUInt8(117)
+    status: buf.readUInt8(117),
+    upTotal: buf.length >= 135 ? buf.readBigUInt64LE(119) : 0n,
+    downTotal: buf.length >= 135 ? buf.readBigUInt64LE(127) : 0n,
+    oracleKind: buf.length >= 359 ? buf.readUInt8(358) : 0
   };
 }
 
@@ -181,6 +206,19 @@ function getOracleAccount(market) {
   return new PublicKey(value);
 }
 
+// Rounds keep the oracle kind they were created with, so lock and settle pass the
+// market's PriceUpdateV2 account (e.g. the sponsored Pyth feed) for V2 rounds.
+function getRoundOracleAccount(market, round) {
+  if (round.oracleKind !== ORACLE_KIND_PRICE_UPDATE_V2) {
+    return getOracleAccount(market);
+  }
+  const value = process.env[market.priceUpdateAccountEnv];
+  if (!value) {
+    throw new Error(`Missing ${market.priceUpdateAccountEnv}`);
+  }
+  return new PublicKey(value);
+}
+
 function assertTreasuryLock(configTreasury) {
   const expected = process.env.PANCHO_EXPECTED_TREASURY_WALLET;
   if (!expected) {
@@ -313,13 +351,15 @@ async function maybeLockRound(connection, payer, configPda, market, roundIdSec)
   if (now < round.lockTs) return;
   if (now > round.lockTs + LOCK_GRACE_SECONDS) return;
 
-  const oraclePrice = getOracleAccount(market);
+  const oraclePrice = getRoundOracleAccount(market, round);
+  const houseKeys = await houseSeedKeys(connection, roundPda, round);
   const ix = new TransactionInstruction({
     programId: PROGRAM_ID,
     keys: [
       { pubkey: configPda, isSigner: false, isWritable: false },
       { pubkey: roundPda, isSigner: false, isWritable: true },
-      { pubkey: oraclePrice, isSigner: false, isWritable: false }
+      { pubkey: oraclePrice, isSigner: false, isWritable: false },
+      ...houseKeys
     ],
     data: encodeNoArgsIx("lock_round")
   });
@@ -327,7 +367,35 @@ async function maybeLockRound(connection, payer, configPda, market, roundIdSec)
   console.log(`[onchain-keeper] locked ${market.key} round ${roundIdSec} tx=${sig}`);
 }
 
-async function maybeSettleRound(connection, payer, configPda, treasury, market, roundIdSec) {
+// House accounts for lock_round: the house vault is always passed; the position, side
+// vault and system program only when exactly one side is empty and the vault is enabled,
+// otherwise the program id stands in for each of them.
+async function houseSeedKeys(connection, roundPda, round) {
+  const placeholders = [
+    { pubkey: HOUSE_VAULT_PDA, isSigner: false, isWritable: true },
+    ...Array.from({ length: 3 }, () => ({ pubkey: PROGRAM_ID, isSigner: false, isWritable: false }))
+  ];
+  if ((round.upTotal === 0n) === (round.downTotal === 0n)) {
+    return placeholders;
+  }
+  const houseInfo = await connection.getAccountInfo(HOUSE_VAULT_PDA, "confirmed");
+  if (!houseInfo || houseInfo.data.length < 9 || houseInfo.data.readUInt8(8) !== 1) {
+    return placeholders;
+  }
+  const side = round.upTotal === 0n ? 0 : 1;
+  const housePosition = PublicKey.findProgramAddressSync(
+    [Buffer.from("position"), roundPda.toBuffer(), HOUSE_VAULT_PDA.toBuffer(), Buffer.from([side])],
+    PROGRAM_ID
+  )[0];
+  return [
+    { pubkey: HOUSE_VAULT_PDA, isSigner: false, isWritable: true },
+    { pubkey: housePosition, isSigner: false, isWritable: true },
+    { pubkey: deriveVaultPda(roundPda, side), isSigner: false, isWritable: true },
+    { pubkey: SYSTEM_PROGRAM_ID, isSigner: false, isWritable: false }
+  ];
+}
+
+async function maybeSettleRound(connection, payer, configPda, config, market, roundIdSec) {
   const roundPda = deriveRoundPda(market.code, BigInt(roundIdSec));
   const info = await connection.getAccountInfo(roundPda, "confirmed");
   if (!info) return;
@@ -339,7 +407,7 @@ async function maybeSettleRound(connection, payer, configPda, treasury, market,
 
   const upVault = deriveVaultPda(roundPda, 0);
   const downVault = deriveVaultPda(roundPda, 1);
-  const oraclePrice = getOracleAccount(market);
+  const oraclePrice = getRoundOracleAccount(market, round);
   const ix = new TransactionInstruction({
     programId: PROGRAM_ID,
     keys: [
@@ -348,12 +416,43 @@ async function maybeSettleRound(connection, payer, configPda, treasury, market,
       { pubkey: upVault, isSigner: false, isWritable: true },
       { pubkey: downVault, isSigner: false, isWritable: true },
       { pubkey: oraclePrice, isSigner: false, isWritable: false },
-      { pubkey: treasury, isSigner: false, isWritable: true }
+      { pubkey: config.treasury, isSigner: false, isWritable: true },
+      // Room host: only room rounds pay one, so keeper-created rounds pass the placeholder.
+      { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
+      // Optional insurance fund: the program id stands in when no insurance share is configured.
+      { pubkey: config.insuranceFeeBps > 0 ? INSURANCE_FUND_PDA : PROGRAM_ID, isSigner: false, isWritable: config.insuranceFeeBps > 0 },
+      ...config.feeSplitRecipients.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true }))
     ],
     data: encodeNoArgsIx("settle_round")
   });
   const sig = await sendIx(connection, payer, ix);
   console.log(`[onchain-keeper] settled ${market.key} round ${roundIdSec} tx=${sig}`);
+  await maybeClaimHouse(connection, payer, market, roundPda, roundIdSec, upVault, downVault);
+}
+
+async function maybeClaimHouse(connection, payer, market, roundPda, roundIdSec, upVault, downVault) {
+  for (const side of [0, 1]) {
+    const housePosition = PublicKey.findProgramAddressSync(
+      [Buffer.from("position"), roundPda.toBuffer(), HOUSE_VAULT_PDA.toBuffer(), Buffer.from([side])],
+      PROGRAM_ID
+    )[0];
+    const info = await connection.getAccountInfo(housePosition, "confirmed");
+    if (!info) continue;
+
+    const ix = new TransactionInstruction({
+      programId: PROGRAM_ID,
+      keys: [
+        { pubkey: roundPda, isSigner: false, isWritable: true },
+        { pubkey: HOUSE_VAULT_PDA, isSigner: false, isWritable: true },
+        { pubkey: housePosition, isSigner: false, isWritable: true },
+        { pubkey: upVault, isSigner: false, isWritable: true },
+        { pubkey: downVault, isSigner: false, isWritable: true }
+      ],
+      data: encodeNoArgsIx("claim_house")
+    });
+    const sig = await sendIx(connection, payer, ix);
+    console.log(`[onchain-keeper] claimed house position ${market.key} round ${roundIdSec} tx=${sig}`);
+  }
 }
 
 async function keeperTick(connection, payer, configPda) {
@@ -364,7 +463,6 @@ async function keeperTick(connection, payer, configPda) {
     return;
   }
 
-  const treasury = config.treasury;
   const nowSec = Math.floor(Date.now() / 1000);
   const rounds = candidateRoundIds(nowSec);
   const currentCycle = Math.floor(nowSec / ENTRY_CYCLE_SECONDS) * ENTRY_CYCLE_SECONDS;
@@ -412,7 +510,7 @@ async function keeperTick(connection, payer, configPda) {
         await maybeLockRound(connection, payer, configPda, market, roundIdSec);
       });
       await safeStep(`settle ${market.key} ${roundIdSec}`, async () => {
-        await maybeSettleRound(connection, payer, configPda, treasury, market, roundIdSec);
+        await maybeSettleRound(connection, payer, configPda, config, market, roundIdSec);
       });
     }
   }